@@ -25,13 +25,28 @@ fn main() {
 
     engine.import_word_list(std_words);
 
+    engine.set_source(file_name);
+
+    // Print a timeline of every executed word to stderr
+    if args.iter().any(|arg| arg == "--trace") {
+        engine.tracing = true;
+        engine.set_trace_hook(|event| {
+            if event.name != "<compile>" && event.name != "<skip>" {
+                eprintln!("{}", event)
+            }
+        });
+    }
+
     if let Ok(lines) = read_lines(file_name) {
-        for line_opt in lines {
-            if let Ok(line) = line_opt {
-                match engine.eval(line) {
-                    Ok(ok) => print!("{}", ok.as_str()),
-                    Err(err) => print!("{}", err.trim_start()),
+        for line in lines.map_while(Result::ok) {
+            match engine.eval(line) {
+                Ok(outcome) => {
+                    print!("{}", outcome.output);
+                    if outcome.status == EvalStatus::Completed && !engine.silent {
+                        print!("\nOk.\n");
+                    }
                 }
+                Err(err) => print!("{}", err.trim_start()),
             }
         }
     }
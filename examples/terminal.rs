@@ -1,3 +1,5 @@
+use std::env;
+
 use sorth::prelude::*;
 
 fn main() {
@@ -7,12 +9,101 @@ fn main() {
 
     engine.import_word_list(std_words);
 
+    if env::args().nth(1).as_deref() == Some("debug") {
+        debug(&mut engine);
+        return;
+    }
+
     while engine.running {
         let mut line = String::new();
         std::io::stdin().read_line(&mut line).unwrap();
         match engine.eval(line) {
-            Ok(ok) => print!("{}", ok.as_str()),
+            Ok(outcome) => {
+                print!("{}", outcome.output);
+                if outcome.status == EvalStatus::Completed && !engine.silent {
+                    print!("\nOk.\n");
+                }
+            }
             Err(err) => print!("{}", err.trim_start()),
         }
     }
 }
+
+// Debug mode
+//
+// Lines are loaded and executed one word at a time. While there are words left
+// to execute the following commands are available:
+//   <empty line>  execute the next word
+//   \c            continue until a breakpoint or the end of the line
+//   \q            discard the rest of the line
+// At any time:
+//   \b <word>     set a breakpoint on <word>
+//   \d <word>     remove the breakpoint on <word>
+fn debug(engine: &mut Engine) {
+    while engine.running {
+        if let Some(token) = engine.next_token() {
+            println!("next: {}", token);
+        }
+
+        let mut line = String::new();
+        if std::io::stdin().read_line(&mut line).unwrap() == 0 {
+            break;
+        }
+        let command = line.trim();
+
+        let res = if let Some(word) = command.strip_prefix("\\b ") {
+            engine.breakpoints.push(word.trim().to_string());
+            Ok(())
+        } else if let Some(word) = command.strip_prefix("\\d ") {
+            engine.breakpoints.retain(|b| b != word.trim());
+            Ok(())
+        } else if engine.waiting_for_input {
+            engine.eval(line).map(|_| ())
+        } else if engine.is_idle() {
+            engine.load(line)
+        } else if command.is_empty() {
+            engine.step().map(|step| {
+                if let Some(step) = step {
+                    print_step(engine, &step);
+                }
+            })
+        } else if command == "\\c" {
+            engine.resume().map(|output| {
+                if !output.is_empty() {
+                    println!("output: {}", output);
+                }
+                print_stacks(engine);
+            })
+        } else if command == "\\q" {
+            engine.unwind();
+            Ok(())
+        } else {
+            Err("Unknown debugger command\n".to_string())
+        };
+
+        if let Err(err) = res {
+            print!("{}", err.trim_start());
+        }
+    }
+}
+
+fn print_step(engine: &Engine, step: &StepInfo) {
+    println!(
+        "{}[{}] {} ({})",
+        "  ".repeat(step.depth),
+        step.word_index,
+        step.token,
+        step.location
+    );
+    if !step.output.is_empty() {
+        println!("output: {}", step.output);
+    }
+    print_stacks(engine);
+}
+
+fn print_stacks(engine: &Engine) {
+    let stack: Vec<String> = engine.main_stack.iter().map(|v| v.to_string()).collect();
+    println!("data: [ {} ]", stack.join(" "));
+    println!("loop: {:?}", engine.loop_stack);
+    println!("cond: {:?}", engine.conditional_stack);
+}
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sorth::prelude::{Engine, EngineLimits, Standard, Types, WordList, MAX_IMAGE_DEPTH};
+
+// The harness lives next to the crate tests so the smoke test and the fuzz
+// target run programs the same way
+#[path = "../../src/tests/fuzz.rs"]
+mod fuzz;
+
+fuzz_target!(|data: &[u8]| fuzz::run(data));
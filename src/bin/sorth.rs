@@ -0,0 +1,159 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use sorth::prelude::*;
+
+const USAGE: &str = "Usage:
+    sorth <file>              run a sorth program
+    sorth test [<path>...]    run the *.test.sorth files in the given files and directories";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("test") => run_tests(&args[1..]),
+        Some(file) if !file.starts_with('-') => run_file(file),
+        _ => {
+            eprintln!("{}", USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn new_engine(source: &str) -> Engine {
+    let mut engine = Engine::new();
+    engine.import_word_list(Standard::new());
+    engine.set_source(source);
+    engine
+}
+
+fn run_file(file: &str) -> ExitCode {
+    let src = match fs::read_to_string(file) {
+        Ok(src) => src,
+        Err(err) => {
+            eprintln!("{}: {}", file, err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut engine = new_engine(file);
+    for line in src.lines() {
+        match engine.eval(line.to_string()) {
+            Ok(outcome) => {
+                print!("{}", outcome.output);
+                if outcome.status == EvalStatus::Exited {
+                    break;
+                }
+            }
+            Err(err) => {
+                eprint!("{}", err.trim_start());
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    println!();
+
+    ExitCode::SUCCESS
+}
+
+fn run_tests(paths: &[String]) -> ExitCode {
+    let mut files = Vec::new();
+    if paths.is_empty() {
+        find_test_files(Path::new("."), &mut files);
+    }
+    for path in paths {
+        find_test_files(Path::new(path), &mut files);
+    }
+    files.sort();
+
+    let (mut passed, mut failed) = (0, 0);
+    for file in files.iter() {
+        let (file_passed, file_failed) = run_test_file(file);
+        passed += file_passed;
+        failed += file_failed;
+    }
+
+    println!(
+        "\n{} files, {} passed, {} failed",
+        files.len(),
+        passed,
+        failed
+    );
+
+    if failed > 0 || files.is_empty() {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+// Collects the *.test.sorth files, skipping hidden and target directories
+fn find_test_files(path: &Path, files: &mut Vec<PathBuf>) {
+    if path.is_file() {
+        files.push(path.to_path_buf());
+        return;
+    }
+
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprintln!("{}: {}", path.display(), err);
+            return;
+        }
+    };
+
+    for entry in entries.map_while(Result::ok) {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        if path.is_dir() {
+            if !name.starts_with('.') && name != "target" {
+                find_test_files(&path, files);
+            }
+        } else if name.ends_with(".test.sorth") {
+            files.push(path);
+        }
+    }
+}
+
+// Errors that aren't raised by the test words count as failures too
+fn run_test_file(file: &Path) -> (u64, u64) {
+    let name = file.display().to_string();
+    let src = match fs::read_to_string(file) {
+        Ok(src) => src,
+        Err(err) => {
+            println!("{}: {}", name, err);
+            return (0, 1);
+        }
+    };
+
+    let mut engine = new_engine(&name);
+    let mut errors = Vec::new();
+    let mut other_errors = 0;
+
+    for line in src.lines() {
+        if let Err(err) = engine.eval(line.to_string()) {
+            if !err.starts_with(TEST_FAILED_ERROR) {
+                other_errors += 1;
+            }
+            errors.push(err);
+            engine.main_stack.clear();
+        }
+    }
+
+    let failed = engine.tests_failed + other_errors;
+    println!(
+        "{}: {} passed, {} failed",
+        name, engine.tests_passed, failed
+    );
+    for err in errors.iter() {
+        for line in err.trim_end().lines() {
+            println!("    {}", line);
+        }
+    }
+
+    (engine.tests_passed, failed)
+}
@@ -0,0 +1,306 @@
+// Fixed-point decimal numbers
+//
+// A Decimal is an integer mantissa and a scale, the number of digits after the
+// decimal point, so 12.50 is 1250 with a scale of 2. Adding, subtracting and
+// multiplying is exact as long as the result fits, dividing keeps up to
+// MAX_SCALE digits. Whenever digits have to be dropped the result is rounded
+// half away from zero, like the `round` word does for floats.
+
+use alloc::{format, string::ToString};
+use core::{cmp::Ordering, fmt, str::FromStr};
+
+use crate::prelude::{Double, Float, Int128};
+
+/// Fixed-point decimal number, `mantissa / 10^scale`.
+#[derive(Clone, Copy, Debug)]
+pub struct Decimal {
+    mantissa: Int128,
+    scale: u32,
+}
+
+/// The string isn't a decimal number or has more than `Decimal::MAX_SCALE`
+/// digits after the point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParseDecimalError;
+
+fn pow10(exp: u32) -> Option<Int128> {
+    (10 as Int128).checked_pow(exp)
+}
+
+// n / d rounded half away from zero
+fn div_round(n: Int128, d: Int128) -> Option<Int128> {
+    let q = n.checked_div(d)?;
+    let r = n.unsigned_abs() % d.unsigned_abs();
+
+    if r != 0 && r >= d.unsigned_abs() - r {
+        q.checked_add(if (n < 0) == (d < 0) { 1 } else { -1 })
+    } else {
+        Some(q)
+    }
+}
+
+impl Decimal {
+    /// Most digits after the decimal point a Decimal can have.
+    pub const MAX_SCALE: u32 = 18;
+
+    /// `None` if the scale is larger than `MAX_SCALE`.
+    pub fn new(mantissa: Int128, scale: u32) -> Option<Decimal> {
+        (scale <= Self::MAX_SCALE).then_some(Decimal { mantissa, scale })
+    }
+
+    pub fn mantissa(&self) -> Int128 {
+        self.mantissa
+    }
+
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mantissa == 0
+    }
+
+    /// The same value with `scale` digits after the point, rounded if digits
+    /// are dropped. `None` if the scale is too large or the value doesn't fit.
+    pub fn rescale(self, scale: u32) -> Option<Decimal> {
+        if scale > Self::MAX_SCALE {
+            return None;
+        }
+
+        let mantissa = if scale >= self.scale {
+            self.mantissa.checked_mul(pow10(scale - self.scale)?)?
+        } else {
+            div_round(self.mantissa, pow10(self.scale - scale)?)?
+        };
+
+        Some(Decimal { mantissa, scale })
+    }
+
+    // Drops trailing zeros down to `scale` digits after the point
+    fn normalize(mut self, scale: u32) -> Decimal {
+        while self.scale > scale && self.mantissa % 10 == 0 {
+            self.mantissa /= 10;
+            self.scale -= 1;
+        }
+        self
+    }
+
+    // Mantissas of both numbers at the larger of their scales
+    fn align(self, other: Decimal) -> Option<(Int128, Int128, u32)> {
+        let scale = self.scale.max(other.scale);
+        Some((
+            self.rescale(scale)?.mantissa,
+            other.rescale(scale)?.mantissa,
+            scale,
+        ))
+    }
+
+    pub fn checked_add(self, other: Decimal) -> Option<Decimal> {
+        let (a, b, scale) = self.align(other)?;
+        Decimal::new(a.checked_add(b)?, scale)
+    }
+
+    pub fn checked_sub(self, other: Decimal) -> Option<Decimal> {
+        let (a, b, scale) = self.align(other)?;
+        Decimal::new(a.checked_sub(b)?, scale)
+    }
+
+    pub fn checked_mul(self, other: Decimal) -> Option<Decimal> {
+        let product = Decimal {
+            mantissa: self.mantissa.checked_mul(other.mantissa)?,
+            scale: self.scale + other.scale,
+        };
+
+        if product.scale > Self::MAX_SCALE {
+            // Can't overflow, the mantissa only gets smaller
+            product.rescale(Self::MAX_SCALE)
+        } else {
+            Some(product)
+        }
+    }
+
+    /// Keeps as many digits as fit up to `MAX_SCALE` and drops trailing zeros
+    /// beyond the scale of the operands. `None` for a zero divisor.
+    pub fn checked_div(self, other: Decimal) -> Option<Decimal> {
+        if other.is_zero() {
+            return None;
+        }
+
+        let min_scale = self.scale.max(other.scale);
+        (min_scale..=Self::MAX_SCALE).rev().find_map(|scale| {
+            let dividend = self
+                .mantissa
+                .checked_mul(pow10(scale + other.scale - self.scale)?)?;
+            let mantissa = div_round(dividend, other.mantissa)?;
+            Some(Decimal { mantissa, scale }.normalize(min_scale))
+        })
+    }
+
+    pub fn checked_rem(self, other: Decimal) -> Option<Decimal> {
+        let (a, b, scale) = self.align(other)?;
+        Decimal::new(a.checked_rem(b)?, scale)
+    }
+
+    pub fn checked_neg(self) -> Option<Decimal> {
+        Decimal::new(self.mantissa.checked_neg()?, self.scale)
+    }
+
+    pub fn checked_abs(self) -> Option<Decimal> {
+        Decimal::new(self.mantissa.checked_abs()?, self.scale)
+    }
+
+    /// Integer part, rounded toward zero.
+    pub fn trunc(self) -> Decimal {
+        // 10^scale always fits
+        Decimal::from(self.mantissa / pow10(self.scale).unwrap())
+    }
+
+    fn has_fraction(&self) -> bool {
+        self.mantissa % pow10(self.scale).unwrap() != 0
+    }
+
+    pub fn floor(self) -> Decimal {
+        match self.trunc() {
+            trunc if self.mantissa < 0 && self.has_fraction() => Decimal::from(trunc.mantissa - 1),
+            trunc => trunc,
+        }
+    }
+
+    pub fn ceil(self) -> Decimal {
+        match self.trunc() {
+            trunc if self.mantissa > 0 && self.has_fraction() => Decimal::from(trunc.mantissa + 1),
+            trunc => trunc,
+        }
+    }
+
+    pub fn round(self) -> Decimal {
+        // Rounding away digits can't overflow
+        self.rescale(0).unwrap()
+    }
+
+    /// The integer part, like the `as` conversion of a float.
+    pub fn to_int128(self) -> Int128 {
+        self.trunc().mantissa
+    }
+
+    pub fn to_double(self) -> Double {
+        // Parsing rounds to the closest double
+        self.to_string().parse().unwrap()
+    }
+
+    pub fn to_float(self) -> Float {
+        self.to_string().parse().unwrap()
+    }
+
+    /// The closest Decimal to the shortest representation of a float, so 0.1
+    /// becomes exactly 0.1. `None` for NaN, infinity and values that don't fit.
+    pub fn from_double(val: Double) -> Option<Decimal> {
+        if !val.is_finite() {
+            return None;
+        }
+        Self::parse_rounded(&format!("{}", val))
+    }
+
+    pub fn from_float(val: Float) -> Option<Decimal> {
+        if !val.is_finite() {
+            return None;
+        }
+        Self::parse_rounded(&format!("{}", val))
+    }
+
+    // Like `from_str`, but rounds digits beyond MAX_SCALE
+    fn parse_rounded(s: &str) -> Option<Decimal> {
+        match s.split_once('.') {
+            Some((int, frac)) if frac.len() > Self::MAX_SCALE as usize => {
+                let digits = &frac[..Self::MAX_SCALE as usize + 1];
+                let precise: Decimal = format!("{}{}", int, digits).parse().ok()?;
+                let precise = Decimal {
+                    mantissa: precise.mantissa,
+                    scale: Self::MAX_SCALE + 1,
+                };
+                Some(precise.rescale(Self::MAX_SCALE)?.normalize(0))
+            }
+            _ => s.parse().ok(),
+        }
+    }
+}
+
+impl From<Int128> for Decimal {
+    fn from(val: Int128) -> Self {
+        Decimal {
+            mantissa: val,
+            scale: 0,
+        }
+    }
+}
+
+impl PartialEq for Decimal {
+    // 1.5 and 1.50 are equal
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.align(*other) {
+            Some((a, b, _)) => a.cmp(&b),
+            // The one that doesn't fit at the larger scale is larger in
+            // magnitude, so its sign decides
+            None if self.scale < other.scale => self.mantissa.cmp(&0),
+            None => 0.cmp(&other.mantissa),
+        }
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+
+        if scale == 0 {
+            return write!(f, "{}{}", sign, digits);
+        }
+
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (int, frac) = digits.split_at(digits.len() - scale);
+        write!(f, "{}{}.{}", sign, int, frac)
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = ParseDecimalError;
+
+    /// Digits with an optional sign and fractional part, `-12.50`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (int, frac) = s.split_once('.').unwrap_or((s, ""));
+        let unsigned = int.strip_prefix(['-', '+']).unwrap_or(int);
+
+        if unsigned.is_empty()
+            || !unsigned.bytes().all(|c| c.is_ascii_digit())
+            || !frac.bytes().all(|c| c.is_ascii_digit())
+            || (s.contains('.') && frac.is_empty())
+            || frac.len() > Self::MAX_SCALE as usize
+        {
+            return Err(ParseDecimalError);
+        }
+
+        let mantissa = format!("{}{}", int, frac)
+            .parse::<Int128>()
+            .map_err(|_| ParseDecimalError)?;
+
+        Ok(Decimal {
+            mantissa,
+            scale: frac.len() as u32,
+        })
+    }
+}
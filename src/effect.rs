@@ -0,0 +1,64 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use crate::prelude::Engine;
+
+/// A stack effect declaration like `( a b -- c )`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StackEffect {
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+}
+
+impl StackEffect {
+    /// Parses a declaration at the start of `tokens`.
+    ///
+    /// Returns the effect and the number of tokens it spans, or `None` if
+    /// `tokens` doesn't start with a comment containing exactly one `--`.
+    pub fn parse(tokens: &[&str]) -> Option<(StackEffect, usize)> {
+        if tokens.first() != Some(&"(") {
+            return None;
+        }
+        let end = tokens.iter().position(|&t| t == ")")?;
+
+        let inner = &tokens[1..end];
+        let mut sides = inner.split(|&t| t == "--");
+        let inputs = sides.next()?.iter().map(|t| t.to_string()).collect();
+        let outputs = sides.next()?.iter().map(|t| t.to_string()).collect();
+        if sides.next().is_some() {
+            return None;
+        }
+
+        Some((StackEffect { inputs, outputs }, end + 1))
+    }
+}
+
+impl fmt::Display for StackEffect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        for input in self.inputs.iter() {
+            write!(f, " {}", input)?;
+        }
+        write!(f, " --")?;
+        for output in self.outputs.iter() {
+            write!(f, " {}", output)?;
+        }
+        write!(f, " )")
+    }
+}
+
+impl Engine {
+    /// The declared stack effect of the custom word `name`.
+    pub fn word_effect(&self, name: &str) -> Option<StackEffect> {
+        let compiled = self
+            .compiled_words
+            .iter()
+            .find(|compiled| compiled.name == name)?;
+
+        let tokens: Vec<&str> = compiled.body.iter().map(String::as_str).collect();
+        StackEffect::parse(&tokens).map(|(effect, _)| effect)
+    }
+}
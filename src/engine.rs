@@ -1,4 +1,20 @@
-use crate::prelude::{Types, Word, WordList, UNKNOWN_WORD_ERROR};
+use crate::{
+    prelude::{
+        CompiledWord, EngineLimits, Location, ProfileEntry, Stack, TraceHook, TracePhase, Types,
+        Word, WordDefinition, WordList, UNBALANCED_LOOP_ERROR, UNKNOWN_WORD_ERROR,
+    },
+    profile::{ProfileFrame, Timer},
+    source::tokenize,
+};
+use alloc::{
+    collections::BTreeMap,
+    format,
+    rc::Rc,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::cell::OnceCell;
 
 pub struct Engine {
     pub running: bool,
@@ -7,22 +23,59 @@ pub struct Engine {
     pub compiled_exec: Vec<bool>,
 
     pub mode: EngineMode,
+    pub arithmetic: Arithmetic,
 
-    pub main_stack: Vec<Types>,
+    pub main_stack: Stack,
 
-    pub variable_stack: Vec<(String, Vec<Types>)>,
+    pub variable_stack: Vec<(String, Stack)>,
     pub conditional_stack: Vec<i8>,
     pub loop_stack: Vec<(i32, i32)>,
+    /// Entries left to visit by each running `map-each`, last entry first.
+    pub map_loops: Vec<Vec<(Types, Types)>>,
+    /// Depths of the loop stack and of `map_loops` when each frame was pushed.
+    pub loop_depths: Vec<(usize, usize)>,
 
     pub curr_line_vec: Vec<Vec<String>>,
+    pub curr_line_locations: Vec<Vec<Location>>,
     pub curr_word_idx: Vec<i32>,
 
+    pub source: Rc<str>,
+    pub source_line: usize,
+
     pub string_buffer: String,
 
-    pub new_compiled_word: String,
-    pub compiled_words: Vec<String>,
+    pub new_compiled_word: Option<CompiledWord>,
+    pub compiled_words: Vec<CompiledWord>,
 
     pub words: Vec<Word>,
+    /// Definitions of the standard words, built the first time a declared
+    /// stack effect is checked.
+    pub(crate) standard_words: OnceCell<Vec<Word>>,
+
+    pub limits: EngineLimits,
+    pub executed_words: u64,
+    /// Bytes of output produced since the last line was loaded.
+    pub output_size: usize,
+
+    pub breakpoints: Vec<String>,
+
+    pub tracing: bool,
+    pub trace_hook: Option<TraceHook>,
+
+    pub profiling: bool,
+    pub profile: BTreeMap<String, ProfileEntry>,
+    pub(crate) profile_frames: Vec<ProfileFrame>,
+
+    /// Stack depth at `T{` and the results taken at `->` of running tests.
+    pub test_stack: Vec<(usize, Option<Vec<Types>>)>,
+    pub tests_passed: u64,
+    pub tests_failed: u64,
+
+    /// Stack depth at each `{` of list literals being built.
+    pub list_stack: Vec<usize>,
+
+    /// Names and field names of the structs declared with `struct`.
+    pub structs: Vec<(String, Vec<String>)>,
 }
 
 #[derive(PartialEq, Debug)]
@@ -34,24 +87,104 @@ pub enum EngineMode {
     STRING,
 }
 
+/// What integer math does when the result doesn't fit its type.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Arithmetic {
+    /// Fail with `OVERFLOW_ERROR`, the default.
+    Checked,
+    /// Wrap around, like two's complement hardware does.
+    Wrapping,
+    /// Clamp to the smallest or largest value of the type.
+    Saturating,
+}
+
+/// State the engine is left in after evaluating a line.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum EvalStatus {
+    /// The line was fully executed.
+    Completed,
+    /// `input` was called, the next line passed to `eval` is the answer.
+    AwaitingInput,
+    /// A word definition was started and not yet closed with `;`.
+    Compiling,
+    /// A comment, string or list was started and not yet closed.
+    Pending,
+    /// `bye` was called.
+    Exited,
+}
+
+/// Result of a successful `Engine::eval` call.
+#[derive(PartialEq, Debug, Clone)]
+pub struct EvalOutcome {
+    /// Text produced by the executed words.
+    pub output: String,
+    pub status: EvalStatus,
+    /// Contents of the main stack after evaluation, bottom first.
+    pub stack: Vec<Types>,
+}
+
+/// State of the engine after executing a single word with `Engine::step`.
+#[derive(PartialEq, Debug, Clone)]
+pub struct StepInfo {
+    /// The word that was executed.
+    pub token: String,
+    /// Number of custom words being executed, 0 for the loaded line itself.
+    pub depth: usize,
+    /// Position of the word in its line or custom word definition.
+    pub word_index: usize,
+    /// Text produced by the word.
+    pub output: String,
+    pub main_stack: Vec<Types>,
+    pub loop_stack: Vec<(i32, i32)>,
+    pub conditional_stack: Vec<i8>,
+    pub location: Location,
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Engine {
     pub fn new() -> Engine {
         Engine {
             running: true,
             mode: EngineMode::NORMAL,
-            main_stack: Vec::new(),
-            new_compiled_word: String::new(),
+            arithmetic: Arithmetic::Checked,
+            main_stack: Stack::new(),
+            new_compiled_word: None,
             compiled_words: Vec::new(),
             words: Vec::new(),
+            standard_words: OnceCell::new(),
             compiled_exec: vec![false],
             conditional_stack: Vec::new(),
             loop_stack: Vec::new(),
+            map_loops: Vec::new(),
+            loop_depths: Vec::new(),
             curr_line_vec: Vec::new(),
+            curr_line_locations: Vec::new(),
+            source: Rc::from("<input>"),
+            source_line: 0,
             curr_word_idx: Vec::new(),
             variable_stack: Vec::new(),
             string_buffer: String::new(),
             waiting_for_input: false,
             silent: false,
+            limits: EngineLimits::default(),
+            executed_words: 0,
+            output_size: 0,
+            breakpoints: Vec::new(),
+            tracing: false,
+            trace_hook: None,
+            profiling: false,
+            profile: BTreeMap::new(),
+            profile_frames: Vec::new(),
+            test_stack: Vec::new(),
+            list_stack: Vec::new(),
+            structs: Vec::new(),
+            tests_passed: 0,
+            tests_failed: 0,
         }
     }
 
@@ -59,82 +192,332 @@ impl Engine {
         self.words.append(&mut word_list.get_words());
     }
 
-    pub fn eval(&mut self, line: String) -> Result<String, String> {
-        if self.waiting_for_input {
+    /// Removes the words with the given names, e.g. `&["input", "bye"]`.
+    pub fn exclude_words(&mut self, names: &[&str]) {
+        self.words.retain(|w| !names.contains(&w.0));
+    }
+
+    /// Removes every word whose name is not in `names`.
+    pub fn retain_words(&mut self, names: &[&str]) {
+        self.words.retain(|w| names.contains(&w.0));
+    }
+
+    /// Replaces the definition of the word `name`.
+    ///
+    /// Returns `false` if there is no word with that name.
+    pub fn override_word(&mut self, name: &str, definition: WordDefinition) -> bool {
+        match self.words.iter_mut().find(|w| w.0 == name) {
+            Some(word) => {
+                word.2 = definition;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sets the name used in the locations of the following tokens and
+    /// restarts the line numbering.
+    pub fn set_source(&mut self, name: &str) {
+        self.source = Rc::from(name);
+        self.source_line = 0;
+    }
+
+    pub fn eval(&mut self, line: String) -> Result<EvalOutcome, String> {
+        let output = if self.waiting_for_input {
             self.main_stack.push(Types::Str(line));
             self.waiting_for_input = false;
-            if !self.silent {
-                return Ok("\nOk.\n".to_string());
-            } else {
-                return Ok("\n".to_string());
+            String::new()
+        } else {
+            let base = self.curr_line_vec.len();
+            self.push_source(&line)?;
+
+            let mut out_buffer = String::new();
+            while let Some(ok) = self.exec_next(base)? {
+                if !out_buffer.ends_with(' ') {
+                    out_buffer.push(' ');
+                }
+                out_buffer += ok.as_str();
             }
+
+            out_buffer.trim_matches(|c| c == ' ').to_string()
+        };
+
+        Ok(EvalOutcome {
+            output,
+            status: self.status(),
+            stack: self.main_stack.to_vec(),
+        })
+    }
+
+    pub fn status(&self) -> EvalStatus {
+        if !self.running {
+            EvalStatus::Exited
+        } else if self.waiting_for_input {
+            EvalStatus::AwaitingInput
+        } else if self.mode_compile() {
+            EvalStatus::Compiling
+        } else if !self.mode_normal() || !self.list_stack.is_empty() {
+            EvalStatus::Pending
+        } else {
+            EvalStatus::Completed
         }
+    }
 
-        let mut out_buffer = String::new();
+    /// Loads a line to be executed word by word with `step` or `resume`.
+    pub fn load(&mut self, line: String) -> Result<(), String> {
+        self.push_source(&line)
+    }
 
-        self.curr_word_idx.push(-1);
+    /// Returns `true` if there are no loaded words left to execute.
+    pub fn is_idle(&self) -> bool {
+        self.next_token().is_none()
+    }
 
+    /// The word that will be executed by the next `step`.
+    pub fn next_token(&self) -> Option<String> {
         self.curr_line_vec
-            .push(line.split_whitespace().map(|w| w.to_string()).collect());
+            .iter()
+            .zip(self.curr_word_idx.iter())
+            .rev()
+            .find(|(line, &idx)| idx + 1 < line.len() as i32)
+            .map(|(line, &idx)| line[(idx + 1) as usize].clone())
+    }
 
-        while *self.curr_word_idx.last_mut().unwrap() + 1
-            < self.curr_line_vec.last().unwrap().len() as i32
-        {
-            if !out_buffer.ends_with(" ") {
-                out_buffer.push_str(" ");
-            }
+    /// Returns `true` if the next word to be executed has a breakpoint set.
+    pub fn at_breakpoint(&self) -> bool {
+        match self.next_token() {
+            Some(token) => self.breakpoints.contains(&token),
+            None => false,
+        }
+    }
 
-            *self.curr_word_idx.last_mut().unwrap() += 1;
-
-            let word_def = self.words.iter().find(|&w| w.0(self));
-            if word_def.is_some() {
-                let res = word_def.unwrap().1(self);
-                match res {
-                    Ok(ok) => {
-                        if !ok.starts_with("\n") || !ok.ends_with("\n") {
-                            out_buffer += ok.trim();
-                        } else {
-                            out_buffer += ok.as_str();
-                        }
-                        continue;
-                    }
-                    Err(err) => return Err(err + "\n"),
-                }
+    /// Executes a single loaded word.
+    ///
+    /// Returns `None` if there was nothing left to execute. On error all
+    /// loaded words are discarded.
+    pub fn step(&mut self) -> Result<Option<StepInfo>, String> {
+        let (token, depth, word_index, location) = match self.next_token() {
+            Some(token) => {
+                let frame = self
+                    .curr_line_vec
+                    .iter()
+                    .zip(self.curr_word_idx.iter())
+                    .rposition(|(line, &idx)| idx + 1 < line.len() as i32)
+                    .unwrap();
+                let word_index = (self.curr_word_idx[frame] + 1) as usize;
+                let location = self.curr_line_locations[frame][word_index].clone();
+                (token, frame, word_index, location)
             }
+            None => return Ok(None),
+        };
 
-            let err_val = UNKNOWN_WORD_ERROR.to_string()
-                + self.curr_line_vec.last().unwrap()
-                    [*self.curr_word_idx.last_mut().unwrap() as usize]
-                    .as_str();
-            self.curr_line_vec.pop();
-            self.curr_word_idx.pop();
-            return Err(err_val + "\n");
-        }
+        let output = match self.exec_next(0)? {
+            Some(output) => output,
+            None => return Ok(None),
+        };
+
+        Ok(Some(StepInfo {
+            token,
+            depth,
+            word_index,
+            output,
+            main_stack: self.main_stack.to_vec(),
+            loop_stack: self.loop_stack.clone(),
+            conditional_stack: self.conditional_stack.clone(),
+            location,
+        }))
+    }
+
+    /// Executes loaded words until a breakpoint is reached or there is nothing
+    /// left to execute. Returns the produced text.
+    pub fn resume(&mut self) -> Result<String, String> {
+        let mut out_buffer = String::new();
+
+        while let Some(ok) = self.exec_next(0)? {
+            if !out_buffer.ends_with(' ') {
+                out_buffer.push(' ');
+            }
+            out_buffer += ok.as_str();
 
-        if !self.compiled_exec.last().unwrap() && self.mode == EngineMode::NORMAL {
-            if !self.silent {
-                out_buffer.push_str("\nOk.\n");
+            if self.at_breakpoint() {
+                break;
             }
-            // else {
-            //     out_buffer.push_str("");
-            // }
         }
 
+        Ok(out_buffer.trim_matches(|c| c == ' ').to_string())
+    }
+
+    /// Discards all loaded words and the state of running loops and conditionals.
+    pub fn unwind(&mut self) {
+        self.curr_line_vec.clear();
+        self.curr_line_locations.clear();
+        self.curr_word_idx.clear();
+        self.compiled_exec.truncate(1);
+        self.loop_depths.clear();
+        self.conditional_stack.clear();
+        self.loop_stack.clear();
+        self.map_loops.clear();
+        self.profile_frames.clear();
+        self.test_stack.clear();
+        self.list_stack.clear();
+    }
+
+    // Loads the next lines of the source
+    fn push_source(&mut self, src: &str) -> Result<(), String> {
+        let (tokens, locations) = tokenize(src, &self.source, self.source_line + 1);
+        self.source_line += src.lines().count().max(1);
+        self.output_size = 0;
+
+        self.push_frame(tokens, locations, false)
+    }
+
+    pub(crate) fn push_frame(
+        &mut self,
+        tokens: Vec<String>,
+        locations: Vec<Location>,
+        compiled: bool,
+    ) -> Result<(), String> {
+        self.check_call_depth()?;
+
+        self.curr_word_idx.push(-1);
+        self.curr_line_vec.push(tokens);
+        self.curr_line_locations.push(locations);
+        self.compiled_exec.push(compiled);
+        self.loop_depths
+            .push((self.loop_stack.len(), self.map_loops.len()));
+
+        Ok(())
+    }
+
+    fn pop_frame(&mut self) {
+        self.profile_return(self.curr_line_vec.len() - 1);
+
         self.curr_line_vec.pop();
+        self.curr_line_locations.pop();
         self.curr_word_idx.pop();
+        self.compiled_exec.pop();
+        self.loop_depths.pop();
+    }
+
+    // Executes the next word of the frames above `base`, dropping the frames
+    // that were fully executed. Returns `None` once all of them are done.
+    fn exec_next(&mut self, base: usize) -> Result<Option<String>, String> {
+        while self.curr_line_vec.len() > base {
+            let result = if *self.curr_word_idx.last().unwrap() + 1
+                < self.curr_line_vec.last().unwrap().len() as i32
+            {
+                *self.curr_word_idx.last_mut().unwrap() += 1;
+
+                self.exec_curr_word().map(Some)
+            } else if self.loop_stack.len() > self.loop_depths.last().unwrap().0
+                || self.map_loops.len() > self.loop_depths.last().unwrap().1
+            {
+                // A `for` or `map-each` without its end
+                Err(UNBALANCED_LOOP_ERROR.to_string())
+            } else {
+                self.pop_frame();
+                continue;
+            };
+
+            return result.map_err(|err| {
+                let err = err + self.call_chain().as_str();
+                self.unwind();
+                err + "\n"
+            });
+        }
+
+        Ok(None)
+    }
+
+    fn exec_curr_word(&mut self) -> Result<String, String> {
+        self.count_executed_word()?;
+
+        let (name, word_def) = match self.words.iter().find(|&w| w.1(self)) {
+            Some(word_def) => (word_def.0, word_def.2),
+            None => return Err(UNKNOWN_WORD_ERROR.to_string() + self.get_curr_word().as_str()),
+        };
+
+        // Words that change the current frame (custom words, loops) are
+        // reported with the token and depth they were called with
+        let trace = self
+            .tracing
+            .then(|| (self.get_curr_word(), self.call_depth()));
+        if let Some((token, depth)) = &trace {
+            self.trace(TracePhase::Before, name, token, *depth);
+        }
+
+        let profile = self.profiling.then(|| {
+            (
+                self.get_curr_word(),
+                self.curr_line_vec.len(),
+                Timer::start(),
+            )
+        });
+
+        let ok = word_def(self).map_err(|err| err.trim_end().to_string())?;
+
+        if let Some((token, frames, timer)) = profile {
+            self.profile_word(name, token, frames, timer);
+        }
+
+        if let Some((token, depth)) = &trace {
+            self.trace(TracePhase::After, name, token, *depth);
+        }
 
-        out_buffer = out_buffer.trim_matches(|c| c == ' ').to_string();
+        self.check_limits()?;
+        self.check_output_limit(&ok)?;
 
-        Ok(out_buffer)
+        if !ok.starts_with('\n') || !ok.ends_with('\n') {
+            Ok(ok.trim().to_string())
+        } else {
+            Ok(ok)
+        }
+    }
+
+    // Location of the current token in every frame, innermost first, along
+    // with the custom word it is in, e.g.
+    //
+    //     at <input>:1:12 in sq
+    //     at <input>:2:1
+    fn call_chain(&self) -> String {
+        let mut out = String::new();
+
+        for frame in (0..self.curr_line_vec.len()).rev() {
+            let idx = self.curr_word_idx[frame];
+            let location = match self.curr_line_locations[frame].get(idx as usize) {
+                Some(location) if idx >= 0 => location,
+                _ => continue,
+            };
+            out += &format!("\n  at {}", location);
+
+            if self.compiled_exec[frame + 1] && frame > 0 {
+                let caller_idx = self.curr_word_idx[frame - 1];
+                if let Some(name) = self.curr_line_vec[frame - 1].get(caller_idx as usize) {
+                    out += &format!(" in {}", name);
+                }
+            }
+        }
+
+        out
     }
 
     pub fn get_compiled_exec(&self) -> bool {
-        self.compiled_exec.last().unwrap().clone()
+        *self.compiled_exec.last().unwrap()
+    }
+
+    /// Location of the word being executed.
+    pub fn get_curr_location(&self) -> Option<Location> {
+        let idx = *self.curr_word_idx.last()?;
+        if idx < 0 {
+            return None;
+        }
+        self.curr_line_locations.last()?.get(idx as usize).cloned()
     }
 
     pub fn get_curr_word(&self) -> String {
         if *self.curr_word_idx.last().unwrap() < 0
-            || *self.curr_word_idx.last().unwrap() > self.curr_line_vec.last().unwrap().len() as i32
+            || *self.curr_word_idx.last().unwrap()
+                >= self.curr_line_vec.last().unwrap().len() as i32
         {
             return "".to_string();
         }
@@ -4,3 +4,25 @@ pub static UNKNOWN_WORD_ERROR: &str = "Error: Unknown word: ";
 pub static VARIABLE_INDEX_OUT_OR_RANGE_ERROR: &str = "Error: Variable index out of range!";
 pub static VARIABLE_NOT_DEFINED: &str = "Error: Variable not defined!";
 pub static TYPE_CONVERSION_FAILURE_ERROR: &str = "Error: Type conversion failed!";
+pub static INVALID_IMAGE_ERROR: &str = "Error: Invalid image!";
+pub static UNSUPPORTED_IMAGE_VERSION_ERROR: &str = "Error: Unsupported image version!";
+pub static LIMIT_EXCEEDED_ERROR: &str = "Error: Limit exceeded: ";
+pub static STACK_EFFECT_ERROR: &str = "Error: Stack effect mismatch: ";
+pub static MISSING_WORD_NAME_ERROR: &str = "Error: Missing word name!";
+pub static MISSING_VARIABLE_NAME_ERROR: &str = "Error: Missing variable name!";
+pub static UNBALANCED_LOOP_ERROR: &str = "Error: Unbalanced loop!";
+pub static DIVISION_BY_ZERO_ERROR: &str = "Error: Division by zero!";
+pub static OVERFLOW_ERROR: &str = "Error: Arithmetic overflow!";
+pub static SHIFT_AMOUNT_ERROR: &str = "Error: Invalid shift amount!";
+pub static NOT_A_FLAG_ERROR: &str = "Error: Not a flag!";
+pub static INVALID_CODE_POINT_ERROR: &str = "Error: Invalid code point!";
+pub static DECIMAL_SCALE_ERROR: &str = "Error: Invalid decimal scale!";
+pub static TEST_FAILED_ERROR: &str = "Error: Test failed: ";
+pub static TEST_SYNTAX_ERROR: &str = "Error: Unbalanced T{ -> }T!";
+pub static LIST_SYNTAX_ERROR: &str = "Error: Unbalanced { }!";
+pub static INDEX_OUT_OF_RANGE_ERROR: &str = "Error: Index out of range!";
+pub static STRUCT_SYNTAX_ERROR: &str = "Error: Malformed struct declaration!";
+pub static LOOP_STACK_UNDERFLOW_ERROR: &str = "Error: Loop control stack underflow!";
+pub static CONDITIONAL_STACK_UNDERFLOW_ERROR: &str = "Error: Conditional stack underflow!";
+pub static LOOP_INCREMENT_ERROR: &str = "Error: Loop increment has to be positive!";
+pub static WORD_NAME_TAKEN_ERROR: &str = "Error: Word name already taken: ";
@@ -0,0 +1,464 @@
+// Engine images
+//
+// An image is a text dump of everything the user built up in an engine:
+// colon words, variables, the main stack and the engine flags. The word
+// lists (primitives) are not part of the image, they have to be imported
+// into the engine before the image is loaded.
+//
+// Layout (whitespace separated, strings are length prefixed `<len>:<bytes>`):
+//
+//     sorth-image <version>
+//     flags <running> <waiting_for_input> <silent> <mode> <arithmetic>
+//     stack <count> <value>...
+//     variables <count> (<name> <count> <value>...)...
+//     words <count> <definition>...
+//     structs <count> (<name> <count> <field>...)...
+//     compiling <0|1> <definition in progress>
+//
+// A value is a type tag followed by its payload, e.g. `i 12` or `s 5:hello`.
+// Floats are stored as their bit patterns so they round-trip exactly. Lists
+// are `list <count> <value>...`, maps `map <count> (<key> <value>)...` and
+// structs `struct <name> <count> (<field> <value>)...`. A handle to a variable
+// is `r <index> <name>`. Values can be nested up to `MAX_IMAGE_DEPTH` deep.
+//
+// A definition is `<name> <span start> <span end> <count> (<token> <location>)...`
+// where a location is `<source> <line> <column>`.
+
+use crate::{
+    errors::{INVALID_IMAGE_ERROR, UNSUPPORTED_IMAGE_VERSION_ERROR},
+    prelude::{
+        Arithmetic, Byte, CompiledWord, Decimal, Double, Engine, EngineMode, Float, Int, Int128,
+        Location, Long, MapKey, Stack, Struct, Types, UInt, ULong, VarRef,
+    },
+};
+use alloc::{
+    collections::BTreeMap,
+    format,
+    rc::Rc,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+pub static IMAGE_MAGIC: &str = "sorth-image";
+pub const IMAGE_VERSION: u32 = 4;
+/// Deepest nesting of lists, maps and structs a loaded image can have.
+pub const MAX_IMAGE_DEPTH: usize = 128;
+
+impl Engine {
+    /// Serializes the user defined state of the engine into an image.
+    pub fn save_image(&self) -> String {
+        let mut out = String::new();
+
+        out += &format!("{} {}\n", IMAGE_MAGIC, IMAGE_VERSION);
+        out += &format!(
+            "flags {} {} {} {} {}\n",
+            self.running as u8,
+            self.waiting_for_input as u8,
+            self.silent as u8,
+            mode_name(&self.mode),
+            arithmetic_name(self.arithmetic)
+        );
+
+        out += &format!("stack {}", self.main_stack.len());
+        for val in self.main_stack.iter() {
+            write_value(&mut out, val);
+        }
+        out.push('\n');
+
+        out += &format!("variables {}", self.variable_stack.len());
+        for (name, values) in self.variable_stack.iter() {
+            out.push(' ');
+            write_str(&mut out, name);
+            out += &format!(" {}", values.len());
+            for val in values.iter() {
+                write_value(&mut out, val);
+            }
+        }
+        out.push('\n');
+
+        out += &format!("words {}", self.compiled_words.len());
+        for word in self.compiled_words.iter() {
+            write_word(&mut out, word);
+        }
+        out.push('\n');
+
+        out += &format!("structs {}", self.structs.len());
+        for (name, fields) in self.structs.iter() {
+            out.push(' ');
+            write_str(&mut out, name);
+            out += &format!(" {}", fields.len());
+            for field in fields.iter() {
+                out.push(' ');
+                write_str(&mut out, field);
+            }
+        }
+        out.push('\n');
+
+        match &self.new_compiled_word {
+            Some(word) => {
+                out += "compiling 1";
+                write_word(&mut out, word);
+            }
+            None => out += "compiling 0",
+        }
+        out.push('\n');
+
+        out
+    }
+
+    /// Replaces the user defined state of the engine with the one stored in `image`.
+    ///
+    /// The engine is left untouched if the image can't be parsed.
+    pub fn load_image(&mut self, image: &str) -> Result<(), String> {
+        let mut reader = Reader {
+            src: image,
+            pos: 0,
+            depth: 0,
+        };
+
+        if reader.token()? != IMAGE_MAGIC {
+            return Err(INVALID_IMAGE_ERROR.to_string());
+        }
+        if reader.number::<u32>()? != IMAGE_VERSION {
+            return Err(UNSUPPORTED_IMAGE_VERSION_ERROR.to_string());
+        }
+
+        reader.expect("flags")?;
+        let running = reader.flag()?;
+        let waiting_for_input = reader.flag()?;
+        let silent = reader.flag()?;
+        let mode = parse_mode(reader.token()?)?;
+        let arithmetic = parse_arithmetic(reader.token()?)?;
+
+        reader.expect("stack")?;
+        let mut main_stack = Stack::new();
+        for _ in 0..reader.number::<usize>()? {
+            main_stack.push(reader.value()?);
+        }
+
+        reader.expect("variables")?;
+        let mut variable_stack = Vec::new();
+        for _ in 0..reader.number::<usize>()? {
+            let name = reader.string()?;
+            let mut values = Stack::new();
+            for _ in 0..reader.number::<usize>()? {
+                values.push(reader.value()?);
+            }
+            variable_stack.push((name, values));
+        }
+
+        reader.expect("words")?;
+        let mut compiled_words = Vec::new();
+        for _ in 0..reader.number::<usize>()? {
+            compiled_words.push(reader.word()?);
+        }
+
+        reader.expect("structs")?;
+        let mut structs = Vec::new();
+        for _ in 0..reader.number::<usize>()? {
+            let name = reader.string()?;
+            let mut fields = Vec::new();
+            for _ in 0..reader.number::<usize>()? {
+                fields.push(reader.string()?);
+            }
+            structs.push((name, fields));
+        }
+
+        reader.expect("compiling")?;
+        let new_compiled_word = match reader.flag()? {
+            true => Some(reader.word()?),
+            false => None,
+        };
+
+        // Only a definition in progress puts the engine in compile mode
+        if (mode == EngineMode::COMPILE) != new_compiled_word.is_some() {
+            return Err(INVALID_IMAGE_ERROR.to_string());
+        }
+
+        self.running = running;
+        self.waiting_for_input = waiting_for_input;
+        self.silent = silent;
+        self.mode = mode;
+        self.arithmetic = arithmetic;
+        self.main_stack = main_stack;
+        self.variable_stack = variable_stack;
+        self.compiled_words = compiled_words;
+        self.structs = structs;
+        self.new_compiled_word = new_compiled_word;
+
+        Ok(())
+    }
+}
+
+fn mode_name(mode: &EngineMode) -> &'static str {
+    match mode {
+        EngineMode::NORMAL => "normal",
+        EngineMode::COMPILE => "compile",
+        EngineMode::SEE => "see",
+        EngineMode::COMMENT => "comment",
+        EngineMode::STRING => "string",
+    }
+}
+
+fn parse_mode(name: &str) -> Result<EngineMode, String> {
+    match name {
+        "normal" => Ok(EngineMode::NORMAL),
+        "compile" => Ok(EngineMode::COMPILE),
+        "see" => Ok(EngineMode::SEE),
+        "comment" => Ok(EngineMode::COMMENT),
+        "string" => Ok(EngineMode::STRING),
+        _ => Err(INVALID_IMAGE_ERROR.to_string()),
+    }
+}
+
+fn arithmetic_name(arithmetic: Arithmetic) -> &'static str {
+    match arithmetic {
+        Arithmetic::Checked => "checked",
+        Arithmetic::Wrapping => "wrapping",
+        Arithmetic::Saturating => "saturating",
+    }
+}
+
+fn parse_arithmetic(name: &str) -> Result<Arithmetic, String> {
+    match name {
+        "checked" => Ok(Arithmetic::Checked),
+        "wrapping" => Ok(Arithmetic::Wrapping),
+        "saturating" => Ok(Arithmetic::Saturating),
+        _ => Err(INVALID_IMAGE_ERROR.to_string()),
+    }
+}
+
+fn write_str(out: &mut String, val: &str) {
+    *out += &format!("{}:{}", val.len(), val);
+}
+
+fn write_location(out: &mut String, location: &Location) {
+    out.push(' ');
+    write_str(out, &location.source);
+    *out += &format!(" {} {}", location.line, location.column);
+}
+
+fn write_word(out: &mut String, word: &CompiledWord) {
+    out.push(' ');
+    write_str(out, &word.name);
+    write_location(out, &word.span.0);
+    write_location(out, &word.span.1);
+    *out += &format!(" {}", word.body.len());
+    for (token, location) in word.body.iter().zip(word.locations.iter()) {
+        out.push(' ');
+        write_str(out, token);
+        write_location(out, location);
+    }
+}
+
+fn write_value(out: &mut String, val: &Types) {
+    match val {
+        Types::Int(a) => *out += &format!(" i {}", a),
+        Types::Long(a) => *out += &format!(" l {}", a),
+        Types::Float(a) => *out += &format!(" f {:x}", a.to_bits()),
+        Types::Double(a) => *out += &format!(" d {:x}", a.to_bits()),
+        Types::Byte(a) => *out += &format!(" b {}", a),
+        Types::UInt(a) => *out += &format!(" u {}", a),
+        Types::ULong(a) => *out += &format!(" ul {}", a),
+        Types::Int128(a) => *out += &format!(" i128 {}", a),
+        #[cfg(feature = "bigint")]
+        Types::BigInt(a) => *out += &format!(" n {}", a),
+        Types::Decimal(a) => *out += &format!(" m {}", a),
+        Types::Bool(a) => *out += &format!(" t {}", *a as u8),
+        Types::Char(a) => *out += &format!(" c {}", *a as u32),
+        Types::Str(a) => {
+            *out += " s ";
+            write_str(out, a);
+        }
+        Types::List(a) => {
+            *out += &format!(" list {}", a.len());
+            for val in a.iter() {
+                write_value(out, val);
+            }
+        }
+        Types::Map(a) => {
+            *out += &format!(" map {}", a.len());
+            for (key, val) in a.iter() {
+                write_value(out, &key.clone().into());
+                write_value(out, val);
+            }
+        }
+        Types::VarRef(a) => {
+            *out += &format!(" r {} ", a.index);
+            write_str(out, &a.name);
+        }
+        Types::Struct(a) => {
+            *out += " struct ";
+            write_str(out, &a.name);
+            *out += &format!(" {}", a.fields.len());
+            for (field, val) in a.fields.iter() {
+                out.push(' ');
+                write_str(out, field);
+                write_value(out, val);
+            }
+        }
+    }
+}
+
+struct Reader<'a> {
+    src: &'a str,
+    pos: usize,
+    // Values being read, the current one included
+    depth: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn skip_whitespace(&mut self) {
+        let rest = &self.src[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn token(&mut self) -> Result<&'a str, String> {
+        self.skip_whitespace();
+        let rest = &self.src[self.pos..];
+        let len = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        if len == 0 {
+            return Err(INVALID_IMAGE_ERROR.to_string());
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), String> {
+        if self.token()? != token {
+            return Err(INVALID_IMAGE_ERROR.to_string());
+        }
+        Ok(())
+    }
+
+    fn number<T: core::str::FromStr>(&mut self) -> Result<T, String> {
+        self.token()?
+            .parse::<T>()
+            .map_err(|_| INVALID_IMAGE_ERROR.to_string())
+    }
+
+    fn flag(&mut self) -> Result<bool, String> {
+        match self.token()? {
+            "0" => Ok(false),
+            "1" => Ok(true),
+            _ => Err(INVALID_IMAGE_ERROR.to_string()),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.skip_whitespace();
+        let rest = &self.src[self.pos..];
+        let colon = rest.find(':').ok_or(INVALID_IMAGE_ERROR.to_string())?;
+        let len = rest[..colon]
+            .parse::<usize>()
+            .map_err(|_| INVALID_IMAGE_ERROR.to_string())?;
+        let end = (colon + 1)
+            .checked_add(len)
+            .ok_or(INVALID_IMAGE_ERROR.to_string())?;
+        let val = rest
+            .get(colon + 1..end)
+            .ok_or(INVALID_IMAGE_ERROR.to_string())?;
+        self.pos += end;
+        Ok(val.to_string())
+    }
+
+    fn bits<T>(
+        &mut self,
+        from_str_radix: fn(&str, u32) -> Result<T, core::num::ParseIntError>,
+    ) -> Result<T, String> {
+        from_str_radix(self.token()?, 16).map_err(|_| INVALID_IMAGE_ERROR.to_string())
+    }
+
+    fn location(&mut self) -> Result<Location, String> {
+        Ok(Location {
+            source: Rc::from(self.string()?),
+            line: self.number::<usize>()?,
+            column: self.number::<usize>()?,
+        })
+    }
+
+    fn word(&mut self) -> Result<CompiledWord, String> {
+        let name = self.string()?;
+        let span = (self.location()?, self.location()?);
+
+        let mut body = Vec::new();
+        let mut locations = Vec::new();
+        for _ in 0..self.number::<usize>()? {
+            body.push(self.string()?);
+            locations.push(self.location()?);
+        }
+
+        Ok(CompiledWord {
+            name,
+            body,
+            locations,
+            span,
+        })
+    }
+
+    // Fails for values nested too deep instead of overflowing the stack
+    fn value(&mut self) -> Result<Types, String> {
+        if self.depth == MAX_IMAGE_DEPTH {
+            return Err(INVALID_IMAGE_ERROR.to_string());
+        }
+
+        self.depth += 1;
+        let val = self.tagged_value();
+        self.depth -= 1;
+        val
+    }
+
+    fn tagged_value(&mut self) -> Result<Types, String> {
+        match self.token()? {
+            "i" => Ok(Types::Int(self.number::<Int>()?)),
+            "l" => Ok(Types::Long(self.number::<Long>()?)),
+            "f" => Ok(Types::Float(Float::from_bits(
+                self.bits(u32::from_str_radix)?,
+            ))),
+            "d" => Ok(Types::Double(Double::from_bits(
+                self.bits(u64::from_str_radix)?,
+            ))),
+            "b" => Ok(Types::Byte(self.number::<Byte>()?)),
+            "u" => Ok(Types::UInt(self.number::<UInt>()?)),
+            "ul" => Ok(Types::ULong(self.number::<ULong>()?)),
+            "i128" => Ok(Types::Int128(self.number::<Int128>()?)),
+            #[cfg(feature = "bigint")]
+            "n" => Ok(Types::BigInt(self.number::<crate::prelude::BigInt>()?)),
+            "m" => Ok(Types::Decimal(self.number::<Decimal>()?)),
+            "t" => Ok(Types::Bool(self.flag()?)),
+            "c" => Ok(Types::Char(
+                char::from_u32(self.number::<u32>()?).ok_or(INVALID_IMAGE_ERROR.to_string())?,
+            )),
+            "s" => Ok(Types::Str(self.string()?)),
+            "list" => {
+                let mut values = Vec::new();
+                for _ in 0..self.number::<usize>()? {
+                    values.push(self.value()?);
+                }
+                Ok(Types::List(values))
+            }
+            "map" => {
+                let mut values = BTreeMap::new();
+                for _ in 0..self.number::<usize>()? {
+                    let key = MapKey::try_from(self.value()?)
+                        .map_err(|_| INVALID_IMAGE_ERROR.to_string())?;
+                    values.insert(key, self.value()?);
+                }
+                Ok(Types::Map(values))
+            }
+            "r" => Ok(Types::VarRef(VarRef {
+                index: self.number::<usize>()?,
+                name: self.string()?,
+            })),
+            "struct" => {
+                let name = self.string()?;
+                let mut fields = Vec::new();
+                for _ in 0..self.number::<usize>()? {
+                    fields.push((self.string()?, self.value()?));
+                }
+                Ok(Types::Struct(Struct { name, fields }))
+            }
+            _ => Err(INVALID_IMAGE_ERROR.to_string()),
+        }
+    }
+}
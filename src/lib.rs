@@ -1,16 +1,36 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 // Execution engine
+pub mod effect;
 pub mod engine;
+pub mod image;
+pub mod limits;
+pub mod profile;
+pub mod source;
+pub mod stack;
 
 // Keyword sets
+pub mod decimal;
 pub mod errors;
 pub mod standard;
+pub mod trace;
 pub mod types;
 pub mod word;
 
 pub mod prelude {
+    pub use crate::decimal::*;
+    pub use crate::effect::*;
     pub use crate::engine::*;
     pub use crate::errors::*;
+    pub use crate::image::*;
+    pub use crate::limits::*;
+    pub use crate::profile::*;
+    pub use crate::source::*;
+    pub use crate::stack::*;
     pub use crate::standard::*;
+    pub use crate::trace::*;
     pub use crate::types::*;
     pub use crate::word::*;
 }
@@ -0,0 +1,151 @@
+use alloc::string::{String, ToString};
+use core::mem::size_of;
+
+use crate::{
+    errors::LIMIT_EXCEEDED_ERROR,
+    prelude::{Engine, MapKey, Types},
+};
+
+/// Resource limits enforced by the engine while evaluating code.
+///
+/// `None` means the resource is not limited. Exceeding any of the limits aborts
+/// the current `eval` with a `LIMIT_EXCEEDED_ERROR`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct EngineLimits {
+    /// Words executed since the engine was created or `reset_usage` was called.
+    pub max_executed_words: Option<u64>,
+    /// Depth of the main stack.
+    pub max_stack_depth: Option<usize>,
+    /// Number of nested `for` and `map-each` loops.
+    pub max_loop_depth: Option<usize>,
+    /// Number of nested colon word calls.
+    pub max_call_depth: Option<usize>,
+    /// Bytes held by values on the main stack and in variables.
+    pub max_memory: Option<usize>,
+    /// Bytes of output produced by a single `eval`.
+    pub max_output: Option<usize>,
+}
+
+impl Types {
+    /// Approximate number of bytes held by the value.
+    pub fn size(&self) -> usize {
+        match self {
+            Types::Str(a) => size_of::<Types>() + a.len(),
+            Types::List(a) => size_of::<Types>() + a.iter().map(|v| v.size()).sum::<usize>(),
+            Types::Map(a) => {
+                let key_size = |key: &MapKey| match key {
+                    MapKey::Str(key) => size_of::<MapKey>() + key.len(),
+                    MapKey::Int(_) => size_of::<MapKey>(),
+                };
+                size_of::<Types>() + a.iter().map(|(k, v)| key_size(k) + v.size()).sum::<usize>()
+            }
+            Types::VarRef(a) => size_of::<Types>() + a.name.len(),
+            Types::Struct(a) => {
+                size_of::<Types>()
+                    + a.name.len()
+                    + a.fields
+                        .iter()
+                        .map(|(f, v)| f.len() + v.size())
+                        .sum::<usize>()
+            }
+            #[cfg(feature = "bigint")]
+            Types::BigInt(a) => size_of::<Types>() + a.bits().div_ceil(8) as usize,
+            _ => size_of::<Types>(),
+        }
+    }
+}
+
+impl Engine {
+    /// Bytes held by values on the main stack and in variables.
+    pub fn memory_usage(&self) -> usize {
+        let variables: usize = self
+            .variable_stack
+            .iter()
+            .map(|(name, values)| name.len() + values.size())
+            .sum();
+
+        self.main_stack.size() + variables
+    }
+
+    /// Resets the executed words counter so that evaluation can continue
+    /// after `max_executed_words` was exceeded.
+    pub fn reset_usage(&mut self) {
+        self.executed_words = 0;
+    }
+
+    /// Clears the stacks and the execution state of the engine, keeping the
+    /// defined words and variables.
+    pub fn reset(&mut self) {
+        self.reset_usage();
+        self.running = true;
+        self.waiting_for_input = false;
+        self.mode = crate::prelude::EngineMode::NORMAL;
+        self.main_stack.clear();
+        self.conditional_stack.clear();
+        self.loop_stack.clear();
+        self.map_loops.clear();
+        self.list_stack.clear();
+        self.string_buffer.clear();
+        self.new_compiled_word = None;
+    }
+
+    pub(crate) fn count_executed_word(&mut self) -> Result<(), String> {
+        self.executed_words += 1;
+
+        match self.limits.max_executed_words {
+            Some(max) if self.executed_words > max => {
+                Err(LIMIT_EXCEEDED_ERROR.to_string() + "executed words")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_call_depth(&self) -> Result<(), String> {
+        match self.limits.max_call_depth {
+            Some(max) if self.curr_line_vec.len() > max => {
+                Err(LIMIT_EXCEEDED_ERROR.to_string() + "call depth")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // Counts the output of a word along with the space separating it from the
+    // output of the previous word
+    pub(crate) fn check_output_limit(&mut self, output: &str) -> Result<(), String> {
+        if !output.is_empty() {
+            self.output_size += output.len() + (self.output_size > 0) as usize;
+        }
+
+        match self.limits.max_output {
+            Some(max) if self.output_size > max => {
+                Err(LIMIT_EXCEEDED_ERROR.to_string() + "output size")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_limits(&self) -> Result<(), String> {
+        let limits = &self.limits;
+
+        if limits
+            .max_stack_depth
+            .is_some_and(|max| self.main_stack.len() > max)
+        {
+            return Err(LIMIT_EXCEEDED_ERROR.to_string() + "stack depth");
+        }
+        if limits
+            .max_loop_depth
+            .is_some_and(|max| self.loop_stack.len() + self.map_loops.len() > max)
+        {
+            return Err(LIMIT_EXCEEDED_ERROR.to_string() + "loop depth");
+        }
+        if limits
+            .max_memory
+            .is_some_and(|max| self.memory_usage() > max)
+        {
+            return Err(LIMIT_EXCEEDED_ERROR.to_string() + "memory");
+        }
+
+        Ok(())
+    }
+}
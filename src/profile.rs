@@ -0,0 +1,161 @@
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::time::Duration;
+
+use crate::prelude::Engine;
+
+/// Statistics collected for a single word while profiling.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProfileEntry {
+    pub calls: u64,
+    /// Words executed by the word including the words of nested custom words.
+    pub inclusive_words: u64,
+    /// Words executed directly by the word.
+    pub exclusive_words: u64,
+    /// Always zero without the `std` feature.
+    pub inclusive_time: Duration,
+    /// Always zero without the `std` feature.
+    pub exclusive_time: Duration,
+}
+
+pub(crate) struct Timer {
+    #[cfg(feature = "std")]
+    start: std::time::Instant,
+}
+
+impl Timer {
+    pub(crate) fn start() -> Timer {
+        Timer {
+            #[cfg(feature = "std")]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    fn elapsed(&self) -> Duration {
+        #[cfg(feature = "std")]
+        return self.start.elapsed();
+        #[cfg(not(feature = "std"))]
+        return Duration::ZERO;
+    }
+}
+
+// Custom word call that hasn't returned yet
+pub(crate) struct ProfileFrame {
+    key: String,
+    frame: usize,
+    start_words: u64,
+    timer: Timer,
+    child_time: Duration,
+}
+
+impl Engine {
+    /// Clears the collected profile.
+    pub fn reset_profile(&mut self) {
+        self.profile = BTreeMap::new();
+        self.profile_frames.clear();
+    }
+
+    /// Formats the collected profile as a table sorted by inclusive time
+    /// (inclusive word count without `std`).
+    pub fn profile_report(&self) -> String {
+        let mut entries: Vec<(&String, &ProfileEntry)> = self.profile.iter().collect();
+        entries.sort_by(|a, b| {
+            b.1.inclusive_time
+                .cmp(&a.1.inclusive_time)
+                .then(b.1.inclusive_words.cmp(&a.1.inclusive_words))
+                .then(a.0.cmp(b.0))
+        });
+
+        let mut out = format!(
+            "{:<20} {:>10} {:>12} {:>12}",
+            "word", "calls", "incl words", "excl words"
+        );
+        if cfg!(feature = "std") {
+            out += &format!(" {:>12} {:>12}", "incl us", "excl us");
+        }
+        out.push('\n');
+
+        for (name, entry) in entries {
+            out += &format!(
+                "{:<20} {:>10} {:>12} {:>12}",
+                name, entry.calls, entry.inclusive_words, entry.exclusive_words
+            );
+            if cfg!(feature = "std") {
+                out += &format!(
+                    " {:>12} {:>12}",
+                    entry.inclusive_time.as_micros(),
+                    entry.exclusive_time.as_micros()
+                );
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+
+    // Called after a word was executed, `frames` is the number of frames
+    // before its execution
+    pub(crate) fn profile_word(&mut self, name: &str, token: String, frames: usize, timer: Timer) {
+        // Literals and other pattern matched words are grouped by their name
+        let key = match name {
+            "<custom word>" => token,
+            _ => name.to_string(),
+        };
+
+        if let Some(parent) = self.profile_frames.last() {
+            if parent.frame + 1 == frames {
+                let parent_key = parent.key.clone();
+                self.profile.entry(parent_key).or_default().exclusive_words += 1;
+            }
+        }
+
+        self.profile.entry(key.clone()).or_default().calls += 1;
+
+        if self.curr_line_vec.len() > frames {
+            self.profile_frames.push(ProfileFrame {
+                key,
+                frame: frames,
+                start_words: self.executed_words - 1,
+                timer,
+                child_time: Duration::ZERO,
+            });
+        } else {
+            let elapsed = timer.elapsed();
+            let entry = self.profile.entry(key).or_default();
+            entry.inclusive_words += 1;
+            entry.exclusive_words += 1;
+            entry.inclusive_time += elapsed;
+            entry.exclusive_time += elapsed;
+        }
+    }
+
+    // Called when the frame at index `frame` is dropped, even when not
+    // profiling so that calls still running when profiling was turned off
+    // don't stay behind
+    pub(crate) fn profile_return(&mut self, frame: usize) {
+        match self.profile_frames.last() {
+            Some(call) if call.frame == frame => {}
+            _ => return,
+        }
+
+        let call = self.profile_frames.pop().unwrap();
+        if !self.profiling {
+            return;
+        }
+
+        let elapsed = call.timer.elapsed();
+
+        let entry = self.profile.entry(call.key).or_default();
+        entry.inclusive_words += self.executed_words - call.start_words;
+        entry.inclusive_time += elapsed;
+        entry.exclusive_time += elapsed.saturating_sub(call.child_time);
+
+        if let Some(parent) = self.profile_frames.last_mut() {
+            parent.child_time += elapsed;
+        }
+    }
+}
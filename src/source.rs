@@ -0,0 +1,62 @@
+use alloc::{rc::Rc, string::String, vec::Vec};
+use core::fmt;
+
+/// Position of a token in the evaluated source.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Location {
+    /// Name set with `Engine::set_source`, `<input>` by default.
+    pub source: Rc<str>,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.source, self.line, self.column)
+    }
+}
+
+/// A custom word defined with `:` and `;`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompiledWord {
+    pub name: String,
+    pub body: Vec<String>,
+    /// Location of every token of the body.
+    pub locations: Vec<Location>,
+    /// Locations of the `:` and `;` the word was defined with.
+    pub span: (Location, Location),
+}
+
+// Splits `src` into whitespace separated tokens, `first_line` is the line
+// number of the first line of `src`
+pub(crate) fn tokenize(
+    src: &str,
+    source: &Rc<str>,
+    first_line: usize,
+) -> (Vec<String>, Vec<Location>) {
+    let mut tokens = Vec::new();
+    let mut locations = Vec::new();
+
+    for (line, text) in src.lines().enumerate() {
+        let mut token = String::new();
+        let mut token_column = 0;
+
+        for (column, c) in text.chars().chain(Some(' ')).enumerate() {
+            if !c.is_whitespace() {
+                if token.is_empty() {
+                    token_column = column + 1;
+                }
+                token.push(c);
+            } else if !token.is_empty() {
+                tokens.push(core::mem::take(&mut token));
+                locations.push(Location {
+                    source: source.clone(),
+                    line: first_line + line,
+                    column: token_column,
+                });
+            }
+        }
+    }
+
+    (tokens, locations)
+}
@@ -0,0 +1,98 @@
+use alloc::vec::Vec;
+use core::{
+    cell::Cell,
+    ops::{Deref, DerefMut},
+};
+
+use crate::prelude::Types;
+
+/// A stack of values that keeps track of the memory they hold.
+///
+/// `push`, `pop`, `split_off` and `clear` update the memory total, any other
+/// change through `DerefMut` makes `size` add up the values again the next
+/// time it is called.
+#[derive(Clone, Debug, Default)]
+pub struct Stack {
+    values: Vec<Types>,
+    size: Cell<Option<usize>>,
+}
+
+impl Stack {
+    pub fn new() -> Stack {
+        Stack {
+            values: Vec::new(),
+            size: Cell::new(Some(0)),
+        }
+    }
+
+    /// Bytes held by the values, see `Types::size`.
+    pub fn size(&self) -> usize {
+        match self.size.get() {
+            Some(size) => size,
+            None => {
+                let size = self.values.iter().map(|v| v.size()).sum();
+                self.size.set(Some(size));
+                size
+            }
+        }
+    }
+
+    pub fn push(&mut self, val: Types) {
+        self.size.set(self.size.get().map(|size| size + val.size()));
+        self.values.push(val);
+    }
+
+    pub fn pop(&mut self) -> Option<Types> {
+        let val = self.values.pop()?;
+        self.size.set(self.size.get().map(|size| size - val.size()));
+        Some(val)
+    }
+
+    pub fn split_off(&mut self, at: usize) -> Vec<Types> {
+        let values = self.values.split_off(at);
+        let removed: usize = values.iter().map(|v| v.size()).sum();
+        self.size.set(self.size.get().map(|size| size - removed));
+        values
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.size.set(Some(0));
+    }
+}
+
+impl Deref for Stack {
+    type Target = Vec<Types>;
+
+    fn deref(&self) -> &Vec<Types> {
+        &self.values
+    }
+}
+
+impl DerefMut for Stack {
+    fn deref_mut(&mut self) -> &mut Vec<Types> {
+        self.size.set(None);
+        &mut self.values
+    }
+}
+
+impl From<Vec<Types>> for Stack {
+    fn from(values: Vec<Types>) -> Stack {
+        Stack {
+            values,
+            size: Cell::new(None),
+        }
+    }
+}
+
+impl PartialEq for Stack {
+    fn eq(&self, other: &Stack) -> bool {
+        self.values == other.values
+    }
+}
+
+impl PartialEq<Vec<Types>> for Stack {
+    fn eq(&self, other: &Vec<Types>) -> bool {
+        self.values == *other
+    }
+}
@@ -0,0 +1,168 @@
+// Bit manipulation
+//
+// Works on the bit patterns of integer values, mixed operands are promoted like
+// in math. Shift amounts can be any integer that isn't negative, shifting by
+// the width of the type or more shifts out every bit.
+
+use crate::{
+    errors::{INVALID_TYPE_ERROR, SHIFT_AMOUNT_ERROR, STACK_UNDERFLOW_ERROR},
+    prelude::{Byte, Engine, Int, Int128, Long, Promoted, Types, UInt, ULong},
+};
+use alloc::string::{String, ToString};
+
+// Applies `$op` to both operands in their common type
+macro_rules! bitwise {
+    ($s:expr, |$a:ident, $b:ident| $op:expr) => {{
+        let b = $s.main_stack.pop();
+        let a = $s.main_stack.pop();
+
+        if a.is_none() || b.is_none() {
+            return Err(STACK_UNDERFLOW_ERROR.to_string());
+        }
+
+        let result = match Types::promote(&a.unwrap(), &b.unwrap()) {
+            Some(Promoted::Byte($a, $b)) => Types::Byte($op),
+            Some(Promoted::Int($a, $b)) => Types::Int($op),
+            Some(Promoted::UInt($a, $b)) => Types::UInt($op),
+            Some(Promoted::Long($a, $b)) => Types::Long($op),
+            Some(Promoted::ULong($a, $b)) => Types::ULong($op),
+            Some(Promoted::Int128($a, $b)) => Types::Int128($op),
+            _ => return Err(INVALID_TYPE_ERROR.to_string()),
+        };
+        $s.main_stack.push(result);
+
+        Ok("".to_string())
+    }};
+}
+
+// Applies `$op` to an integer of any width, keeping its type
+macro_rules! per_int {
+    ($val:expr, |$a:ident| $op:expr) => {
+        match $val {
+            Types::Byte($a) => Types::Byte($op),
+            Types::Int($a) => Types::Int($op),
+            Types::UInt($a) => Types::UInt($op),
+            Types::Long($a) => Types::Long($op),
+            Types::ULong($a) => Types::ULong($op),
+            Types::Int128($a) => Types::Int128($op),
+            _ => return Err(INVALID_TYPE_ERROR.to_string()),
+        }
+    };
+}
+
+// Pops the shift amount and then the value to shift
+fn shift_operands(s: &mut Engine) -> Result<(Types, u32), String> {
+    let amount = s.main_stack.pop();
+    let val = s.main_stack.pop();
+
+    if amount.is_none() || val.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    let amount = match amount.unwrap() {
+        Types::Byte(a) => a as Int128,
+        Types::Int(a) => a as Int128,
+        Types::UInt(a) => a as Int128,
+        Types::Long(a) => a as Int128,
+        Types::ULong(a) => a as Int128,
+        Types::Int128(a) => a,
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    };
+    let amount = u32::try_from(amount).map_err(|_| SHIFT_AMOUNT_ERROR.to_string())?;
+
+    Ok((val.unwrap(), amount))
+}
+
+pub fn band(s: &mut Engine) -> Result<String, String> {
+    bitwise!(s, |a, b| a & b)
+}
+
+pub fn bor(s: &mut Engine) -> Result<String, String> {
+    bitwise!(s, |a, b| a | b)
+}
+
+pub fn bxor(s: &mut Engine) -> Result<String, String> {
+    bitwise!(s, |a, b| a ^ b)
+}
+
+pub fn binvert(s: &mut Engine) -> Result<String, String> {
+    let a = s.main_stack.pop();
+
+    if a.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    let result = per_int!(a.unwrap(), |a| !a);
+    s.main_stack.push(result);
+
+    Ok("".to_string())
+}
+
+pub fn popcount(s: &mut Engine) -> Result<String, String> {
+    let a = s.main_stack.pop();
+
+    if a.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    let ones = match a.unwrap() {
+        Types::Byte(a) => a.count_ones(),
+        Types::Int(a) => a.count_ones(),
+        Types::UInt(a) => a.count_ones(),
+        Types::Long(a) => a.count_ones(),
+        Types::ULong(a) => a.count_ones(),
+        Types::Int128(a) => a.count_ones(),
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    };
+    s.main_stack.push(Types::Int(ones as Int));
+
+    Ok("".to_string())
+}
+
+pub fn lshift(s: &mut Engine) -> Result<String, String> {
+    let (val, n) = shift_operands(s)?;
+    s.main_stack
+        .push(per_int!(val, |a| a.checked_shl(n).unwrap_or(0)));
+    Ok("".to_string())
+}
+
+// Shifts in zeros, also for negative values
+pub fn rshift(s: &mut Engine) -> Result<String, String> {
+    let (val, n) = shift_operands(s)?;
+    let result = match val {
+        Types::Int(a) => Types::Int((a as UInt).checked_shr(n).unwrap_or(0) as Int),
+        Types::Long(a) => Types::Long((a as ULong).checked_shr(n).unwrap_or(0) as Long),
+        Types::Int128(a) => Types::Int128((a as u128).checked_shr(n).unwrap_or(0) as Int128),
+        val => per_int!(val, |a| a.checked_shr(n).unwrap_or(0)),
+    };
+    s.main_stack.push(result);
+    Ok("".to_string())
+}
+
+// Shifts in copies of the highest bit, for unsigned values too
+pub fn arshift(s: &mut Engine) -> Result<String, String> {
+    let (val, n) = shift_operands(s)?;
+    let result = match val {
+        Types::Byte(a) => Types::Byte(((a as i8) >> n.min(7)) as Byte),
+        Types::Int(a) => Types::Int(a >> n.min(31)),
+        Types::UInt(a) => Types::UInt(((a as Int) >> n.min(31)) as UInt),
+        Types::Long(a) => Types::Long(a >> n.min(63)),
+        Types::ULong(a) => Types::ULong(((a as Long) >> n.min(63)) as ULong),
+        Types::Int128(a) => Types::Int128(a >> n.min(127)),
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    };
+    s.main_stack.push(result);
+    Ok("".to_string())
+}
+
+pub fn rotl(s: &mut Engine) -> Result<String, String> {
+    let (val, n) = shift_operands(s)?;
+    s.main_stack.push(per_int!(val, |a| a.rotate_left(n)));
+    Ok("".to_string())
+}
+
+pub fn rotr(s: &mut Engine) -> Result<String, String> {
+    let (val, n) = shift_operands(s)?;
+    s.main_stack.push(per_int!(val, |a| a.rotate_right(n)));
+    Ok("".to_string())
+}
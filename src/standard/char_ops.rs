@@ -0,0 +1,138 @@
+use crate::{
+    errors::{INVALID_CODE_POINT_ERROR, INVALID_TYPE_ERROR, STACK_UNDERFLOW_ERROR},
+    prelude::{Engine, Int, Int128, Types},
+};
+use alloc::string::{String, ToString};
+#[cfg(feature = "bigint")]
+use num_traits::ToPrimitive;
+
+// The character with the code point of an integer, fails for numbers that
+// aren't a Unicode scalar value
+pub fn code_point(val: &Types) -> Result<char, String> {
+    let val = match val {
+        Types::Byte(a) => *a as Int128,
+        Types::Int(a) => *a as Int128,
+        Types::UInt(a) => *a as Int128,
+        Types::Long(a) => *a as Int128,
+        Types::ULong(a) => *a as Int128,
+        Types::Int128(a) => *a,
+        #[cfg(feature = "bigint")]
+        Types::BigInt(a) => a.to_i128().unwrap_or(-1),
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    };
+
+    u32::try_from(val)
+        .ok()
+        .and_then(char::from_u32)
+        .ok_or(INVALID_CODE_POINT_ERROR.to_string())
+}
+
+// Pushes every character of a string
+pub fn chars(s: &mut Engine) -> Result<String, String> {
+    let a = s.main_stack.pop();
+
+    if a.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    match a.unwrap() {
+        Types::Str(a) => {
+            for c in a.chars() {
+                s.main_stack.push(Types::Char(c));
+            }
+        }
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    }
+
+    Ok("".to_string())
+}
+
+pub fn char_to_int(s: &mut Engine) -> Result<String, String> {
+    let a = s.main_stack.pop();
+
+    if a.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    match a.unwrap() {
+        Types::Char(a) => s.main_stack.push(Types::Int(a as Int)),
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    }
+
+    Ok("".to_string())
+}
+
+pub fn int_to_char(s: &mut Engine) -> Result<String, String> {
+    let a = s.main_stack.pop();
+
+    if a.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    let c = code_point(&a.unwrap())?;
+    s.main_stack.push(Types::Char(c));
+
+    Ok("".to_string())
+}
+
+// Pops a character and pushes whether it is in the class `test` checks for
+fn classify(s: &mut Engine, test: fn(char) -> bool) -> Result<String, String> {
+    let a = s.main_stack.pop();
+
+    if a.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    match a.unwrap() {
+        Types::Char(a) => s.main_stack.push(Types::Bool(test(a))),
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    }
+
+    Ok("".to_string())
+}
+
+pub fn is_alpha(s: &mut Engine) -> Result<String, String> {
+    classify(s, char::is_alphabetic)
+}
+
+pub fn is_digit(s: &mut Engine) -> Result<String, String> {
+    classify(s, char::is_numeric)
+}
+
+pub fn is_space(s: &mut Engine) -> Result<String, String> {
+    classify(s, char::is_whitespace)
+}
+
+// Changes the case of a string or a character. Some characters change into
+// several, 'ß' upper gives "SS", those give a string.
+fn change_case(s: &mut Engine, convert: fn(&str) -> String) -> Result<String, String> {
+    let a = s.main_stack.pop();
+
+    if a.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    let result = match a.unwrap() {
+        Types::Str(a) => Types::Str(convert(&a)),
+        Types::Char(a) => {
+            let converted = convert(&a.to_string());
+            let mut chars = converted.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Types::Char(c),
+                _ => Types::Str(converted),
+            }
+        }
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    };
+    s.main_stack.push(result);
+
+    Ok("".to_string())
+}
+
+pub fn upper(s: &mut Engine) -> Result<String, String> {
+    change_case(s, str::to_uppercase)
+}
+
+pub fn lower(s: &mut Engine) -> Result<String, String> {
+    change_case(s, str::to_lowercase)
+}
@@ -1,4 +1,5 @@
 use crate::prelude::{Engine, EngineMode};
+use alloc::string::{String, ToString};
 
 pub fn comment_toggle(s: &mut Engine) -> Result<String, String> {
     if s.mode_normal() {
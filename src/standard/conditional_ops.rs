@@ -1,7 +1,8 @@
 use crate::{
-    errors::{INVALID_TYPE_ERROR, STACK_UNDERFLOW_ERROR},
-    prelude::{Engine, Types},
+    errors::{CONDITIONAL_STACK_UNDERFLOW_ERROR, NOT_A_FLAG_ERROR, STACK_UNDERFLOW_ERROR},
+    prelude::Engine,
 };
+use alloc::string::{String, ToString};
 
 pub fn current_cond(s: &Engine) -> i8 {
     match s.conditional_stack.last() {
@@ -17,17 +18,10 @@ pub fn if_word(s: &mut Engine) -> Result<String, String> {
     }
 
     let cond = s.main_stack.pop();
-    match cond {
-        Some(cond_val) => match cond_val {
-            Types::Int(val) => {
-                if val == -1 {
-                    s.conditional_stack.push(1);
-                } else if val == 0 {
-                    s.conditional_stack.push(2);
-                }
-            }
-            _ => return Err(INVALID_TYPE_ERROR.to_string()),
-        },
+    match cond.map(|cond_val| cond_val.truthy()) {
+        Some(Some(true)) => s.conditional_stack.push(1),
+        Some(Some(false)) => s.conditional_stack.push(2),
+        Some(None) => return Err(NOT_A_FLAG_ERROR.to_string()),
         None => return Err(STACK_UNDERFLOW_ERROR.to_string()),
     }
     Ok("".to_string())
@@ -37,9 +31,9 @@ pub fn else_word(s: &mut Engine) -> Result<String, String> {
     let cond = s.conditional_stack.pop();
     match cond {
         Some(cond_val) => {
-            s.conditional_stack.push(cond_val - 1);
+            s.conditional_stack.push(cond_val.saturating_sub(1));
         }
-        None => return Err("Error Conditional stack underflow!".to_string()),
+        None => return Err(CONDITIONAL_STACK_UNDERFLOW_ERROR.to_string()),
     }
     Ok("".to_string())
 }
@@ -48,7 +42,7 @@ pub fn then_word(s: &mut Engine) -> Result<String, String> {
     let cond = s.conditional_stack.pop();
     match cond {
         Some(_) => {}
-        None => return Err("Error Conditional stack underflow!".to_string()),
+        None => return Err(CONDITIONAL_STACK_UNDERFLOW_ERROR.to_string()),
     }
     Ok("".to_string())
 }
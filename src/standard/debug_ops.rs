@@ -0,0 +1,26 @@
+use crate::prelude::Engine;
+use alloc::string::{String, ToString};
+
+pub fn trace_word(s: &mut Engine) -> Result<String, String> {
+    s.tracing = true;
+    Ok("".to_string())
+}
+
+pub fn notrace_word(s: &mut Engine) -> Result<String, String> {
+    s.tracing = false;
+    Ok("".to_string())
+}
+
+pub fn profile_word(s: &mut Engine) -> Result<String, String> {
+    s.profiling = true;
+    Ok("".to_string())
+}
+
+pub fn noprofile_word(s: &mut Engine) -> Result<String, String> {
+    s.profiling = false;
+    Ok("".to_string())
+}
+
+pub fn profile_report_word(s: &mut Engine) -> Result<String, String> {
+    Ok("\n".to_string() + s.profile_report().as_str())
+}
@@ -0,0 +1,247 @@
+use super::{
+    struct_ops::struct_effect,
+    value_ops::{is_char, is_number},
+};
+use crate::{
+    errors::STACK_EFFECT_ERROR,
+    prelude::{Engine, StackEffect, Standard, Word, WordList},
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::ptr::fn_addr_eq;
+
+// Number of values taken and left by the standard words with a fixed effect
+fn standard_effect(word: &str) -> Option<(usize, usize)> {
+    match word {
+        "bye" | "nl" | "trace" | "notrace" | "profile" | "noprofile" | "profile-report"
+        | "checked" | "wrapping" | "saturating" => Some((0, 0)),
+        "i" | "pi" | "e" | "true" | "false" | "map-new" => Some((0, 1)),
+        "." | "drop" | "emit" | "silent" | "assert" => Some((1, 0)),
+        "peek" | "abs" | "not" | "pop" | "len" | "to_int" | "to_long" | "to_float"
+        | "to_double" | "to_byte" | "to_uint" | "to_ulong" | "to_int128" | "to_bigint"
+        | "to_decimal" | "to_str" | "negate" | "sqrt" | "exp" | "ln" | "log10" | "sin" | "cos"
+        | "tan" | "floor" | "ceil" | "round" | "trunc" | "binvert" | "popcount" | "char>int"
+        | "int>char" | "alpha?" | "digit?" | "space?" | "upper" | "lower" | "reverse" | "sort"
+        | "length" | "var>list" | "map-keys" | "map-values" => Some((1, 1)),
+        "dup" => Some((1, 2)),
+        "push" | "assert-eq" | "list>var" => Some((2, 0)),
+        "+" | "-" | "*" | "/" | "%" | "==" | "!=" | "and" | "or" | ">" | "<" | "get" | "concat"
+        | "min" | "max" | "pow" | "atan2" | "hypot" | "band" | "bor" | "bxor" | "lshift"
+        | "rshift" | "arshift" | "rotl" | "rotr" | "rescale" | "index" | "append" | "map-has?"
+        | "map-remove" => Some((2, 1)),
+        "slice" | "map-put" | "map-get" => Some((3, 1)),
+        "swap" => Some((2, 2)),
+        "2dup" => Some((2, 4)),
+        "set" => Some((3, 0)),
+        "rot" => Some((3, 3)),
+        _ => None,
+    }
+}
+
+// The effect of a standard word, if the engine runs its standard definition.
+// Words that were excluded or overridden have an unknown effect.
+fn primitive_effect(s: &Engine, standard: &[Word], word: &str) -> Option<(usize, usize)> {
+    let effect = standard_effect(word)?;
+
+    let current = s.words.iter().find(|w| w.0 == word)?;
+    let original = standard.iter().find(|w| w.0 == word)?;
+
+    fn_addr_eq(current.2, original.2).then_some(effect)
+}
+
+enum Block {
+    If { start: usize, first: Option<usize> },
+    For(usize),
+    MapEach(usize),
+    While(usize),
+    Do { start: usize, exit: usize },
+}
+
+// Checks that `body` has the declared stack effect of the custom word `name`.
+//
+// Only the number of values is checked. Words with an unknown effect (custom
+// words without a declaration, words that leave a variable number of values,
+// host words) end the check without an error.
+pub fn check_effect(
+    s: &Engine,
+    name: &str,
+    effect: &StackEffect,
+    body: &[&str],
+) -> Result<(), String> {
+    let error = |msg: String| {
+        Err(format!(
+            "{}{} {}: {}",
+            STACK_EFFECT_ERROR, name, effect, msg
+        ))
+    };
+
+    let standard = s.standard_words.get_or_init(|| Standard::new().get_words());
+
+    let mut depth = effect.inputs.len();
+    let mut blocks = Vec::new();
+
+    let mut tokens = body.iter();
+    while let Some(&token) = tokens.next() {
+        let (inputs, outputs) = match token {
+            "(" => {
+                for &t in tokens.by_ref() {
+                    if t == ")" || t == "(" {
+                        break;
+                    }
+                }
+                continue;
+            }
+            _ if token.starts_with('"') && token.ends_with('"') => {
+                for &t in tokens.by_ref() {
+                    if t.starts_with('"') && t.ends_with('"') {
+                        break;
+                    }
+                }
+                (0, 1)
+            }
+            "let" => {
+                tokens.next();
+                continue;
+            }
+            "if" => {
+                if depth < 1 {
+                    return error(format!("stack underflow at {}", token));
+                }
+                depth -= 1;
+                blocks.push(Block::If {
+                    start: depth,
+                    first: None,
+                });
+                continue;
+            }
+            "else" => {
+                match blocks.last_mut() {
+                    Some(Block::If { start, first }) if first.is_none() => {
+                        *first = Some(depth);
+                        depth = *start;
+                    }
+                    _ => return Ok(()),
+                }
+                continue;
+            }
+            "then" => {
+                match blocks.pop() {
+                    Some(Block::If { start, first }) => {
+                        let other = first.unwrap_or(start);
+                        if other != depth {
+                            return error(format!(
+                                "branches of if end with stack depth {} and {}",
+                                other, depth
+                            ));
+                        }
+                    }
+                    _ => return Ok(()),
+                }
+                continue;
+            }
+            "for" => {
+                if depth < 2 {
+                    return error(format!("stack underflow at {}", token));
+                }
+                depth -= 2;
+                blocks.push(Block::For(depth));
+                continue;
+            }
+            "next" | "bynext" => {
+                if token == "bynext" {
+                    if depth < 1 {
+                        return error(format!("stack underflow at {}", token));
+                    }
+                    depth -= 1;
+                }
+                match blocks.pop() {
+                    Some(Block::For(start)) if start != depth => {
+                        return error("for loop changes the stack depth".to_string())
+                    }
+                    Some(Block::For(_)) => {}
+                    _ => return Ok(()),
+                }
+                continue;
+            }
+            "map-each" => {
+                if depth < 1 {
+                    return error(format!("stack underflow at {}", token));
+                }
+                depth -= 1;
+                blocks.push(Block::MapEach(depth));
+                // The key and the value of each entry
+                depth += 2;
+                continue;
+            }
+            "map-next" => {
+                match blocks.pop() {
+                    Some(Block::MapEach(start)) if start != depth => {
+                        return error("map-each loop changes the stack depth".to_string())
+                    }
+                    Some(Block::MapEach(_)) => {}
+                    _ => return Ok(()),
+                }
+                continue;
+            }
+            "while" => {
+                blocks.push(Block::While(depth));
+                continue;
+            }
+            "do" => {
+                if depth < 1 {
+                    return error(format!("stack underflow at {}", token));
+                }
+                depth -= 1;
+                match blocks.pop() {
+                    Some(Block::While(start)) => blocks.push(Block::Do { start, exit: depth }),
+                    _ => return Ok(()),
+                }
+                continue;
+            }
+            "again" => {
+                match blocks.pop() {
+                    Some(Block::Do { start, .. }) if start != depth => {
+                        return error("while loop changes the stack depth".to_string())
+                    }
+                    Some(Block::Do { exit, .. }) => depth = exit,
+                    _ => return Ok(()),
+                }
+                continue;
+            }
+            _ if token.starts_with('@') => (0, 1),
+            _ => {
+                if let Some(effect) = primitive_effect(s, standard, token) {
+                    effect
+                } else if is_number(token) || is_char(token) {
+                    (0, 1)
+                } else if token == name {
+                    (effect.inputs.len(), effect.outputs.len())
+                } else if let Some(effect) = s.word_effect(token) {
+                    (effect.inputs.len(), effect.outputs.len())
+                } else if let Some(effect) = struct_effect(s, token) {
+                    effect
+                } else {
+                    return Ok(());
+                }
+            }
+        };
+
+        if depth < inputs {
+            return error(format!("stack underflow at {}", token));
+        }
+        depth = depth - inputs + outputs;
+    }
+
+    if !blocks.is_empty() {
+        return Ok(());
+    }
+
+    if depth != effect.outputs.len() {
+        return error(format!("ends with stack depth {}", depth));
+    }
+
+    Ok(())
+}
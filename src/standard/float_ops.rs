@@ -0,0 +1,218 @@
+// Floating point functions
+//
+// Integers and Decimals are converted to Double, Floats stay Floats. The
+// functions come from std, without it from libm.
+
+use crate::{
+    errors::{INVALID_TYPE_ERROR, STACK_UNDERFLOW_ERROR},
+    prelude::{Decimal, Double, Engine, Float, Promoted, Types},
+};
+use alloc::string::{String, ToString};
+#[cfg(feature = "bigint")]
+use num_traits::ToPrimitive;
+
+#[cfg(not(feature = "std"))]
+use libm as float;
+
+#[cfg(feature = "std")]
+mod float {
+    // Same names as in libm
+    pub fn sqrt(x: f64) -> f64 {
+        x.sqrt()
+    }
+    pub fn pow(x: f64, y: f64) -> f64 {
+        x.powf(y)
+    }
+    pub fn exp(x: f64) -> f64 {
+        x.exp()
+    }
+    pub fn log(x: f64) -> f64 {
+        x.ln()
+    }
+    pub fn log10(x: f64) -> f64 {
+        x.log10()
+    }
+    pub fn sin(x: f64) -> f64 {
+        x.sin()
+    }
+    pub fn cos(x: f64) -> f64 {
+        x.cos()
+    }
+    pub fn tan(x: f64) -> f64 {
+        x.tan()
+    }
+    pub fn atan2(y: f64, x: f64) -> f64 {
+        y.atan2(x)
+    }
+    pub fn hypot(x: f64, y: f64) -> f64 {
+        x.hypot(y)
+    }
+    pub fn floor(x: f64) -> f64 {
+        x.floor()
+    }
+    pub fn ceil(x: f64) -> f64 {
+        x.ceil()
+    }
+    pub fn round(x: f64) -> f64 {
+        x.round()
+    }
+    pub fn trunc(x: f64) -> f64 {
+        x.trunc()
+    }
+}
+
+fn unary(s: &mut Engine, f: fn(Double) -> Double) -> Result<String, String> {
+    let a = s.main_stack.pop();
+
+    if a.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    let result = match a.unwrap() {
+        Types::Float(a) => Types::Float(f(a as Double) as Float),
+        Types::Double(a) => Types::Double(f(a)),
+        Types::Byte(a) => Types::Double(f(a as Double)),
+        Types::Int(a) => Types::Double(f(a as Double)),
+        Types::UInt(a) => Types::Double(f(a as Double)),
+        Types::Long(a) => Types::Double(f(a as Double)),
+        Types::ULong(a) => Types::Double(f(a as Double)),
+        Types::Int128(a) => Types::Double(f(a as Double)),
+        #[cfg(feature = "bigint")]
+        Types::BigInt(a) => Types::Double(f(a.to_f64().unwrap_or(Double::NAN))),
+        Types::Decimal(a) => Types::Double(f(a.to_double())),
+        Types::Bool(_)
+        | Types::Char(_)
+        | Types::Str(_)
+        | Types::List(_)
+        | Types::Map(_)
+        | Types::Struct(_)
+        | Types::VarRef(_) => return Err(INVALID_TYPE_ERROR.to_string()),
+    };
+    s.main_stack.push(result);
+
+    Ok("".to_string())
+}
+
+// Rounding leaves integers as they are, decimals are rounded exactly
+fn rounding(
+    s: &mut Engine,
+    f: fn(Double) -> Double,
+    decimal: fn(Decimal) -> Decimal,
+) -> Result<String, String> {
+    match s.main_stack.last() {
+        Some(
+            Types::Byte(_)
+            | Types::Int(_)
+            | Types::UInt(_)
+            | Types::Long(_)
+            | Types::ULong(_)
+            | Types::Int128(_),
+        ) => Ok("".to_string()),
+        #[cfg(feature = "bigint")]
+        Some(Types::BigInt(_)) => Ok("".to_string()),
+        Some(Types::Decimal(a)) => {
+            let rounded = decimal(*a);
+            s.main_stack.pop();
+            s.main_stack.push(Types::Decimal(rounded));
+            Ok("".to_string())
+        }
+        _ => unary(s, f),
+    }
+}
+
+fn binary(s: &mut Engine, f: fn(Double, Double) -> Double) -> Result<String, String> {
+    let b = s.main_stack.pop();
+    let a = s.main_stack.pop();
+
+    if a.is_none() || b.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    let result = match Types::promote(&a.unwrap(), &b.unwrap()) {
+        Some(Promoted::Float(a, b)) => Types::Float(f(a as Double, b as Double) as Float),
+        Some(Promoted::Double(a, b)) => Types::Double(f(a, b)),
+        Some(Promoted::Byte(a, b)) => Types::Double(f(a as Double, b as Double)),
+        Some(Promoted::Int(a, b)) => Types::Double(f(a as Double, b as Double)),
+        Some(Promoted::UInt(a, b)) => Types::Double(f(a as Double, b as Double)),
+        Some(Promoted::Long(a, b)) => Types::Double(f(a as Double, b as Double)),
+        Some(Promoted::ULong(a, b)) => Types::Double(f(a as Double, b as Double)),
+        Some(Promoted::Int128(a, b)) => Types::Double(f(a as Double, b as Double)),
+        #[cfg(feature = "bigint")]
+        Some(Promoted::BigInt(a, b)) => Types::Double(f(
+            a.to_f64().unwrap_or(Double::NAN),
+            b.to_f64().unwrap_or(Double::NAN),
+        )),
+        Some(Promoted::Decimal(a, b)) => Types::Double(f(a.to_double(), b.to_double())),
+        None => return Err(INVALID_TYPE_ERROR.to_string()),
+    };
+    s.main_stack.push(result);
+
+    Ok("".to_string())
+}
+
+pub fn sqrt(s: &mut Engine) -> Result<String, String> {
+    unary(s, float::sqrt)
+}
+
+pub fn pow(s: &mut Engine) -> Result<String, String> {
+    binary(s, float::pow)
+}
+
+pub fn exp(s: &mut Engine) -> Result<String, String> {
+    unary(s, float::exp)
+}
+
+pub fn ln(s: &mut Engine) -> Result<String, String> {
+    unary(s, float::log)
+}
+
+pub fn log10(s: &mut Engine) -> Result<String, String> {
+    unary(s, float::log10)
+}
+
+pub fn sin(s: &mut Engine) -> Result<String, String> {
+    unary(s, float::sin)
+}
+
+pub fn cos(s: &mut Engine) -> Result<String, String> {
+    unary(s, float::cos)
+}
+
+pub fn tan(s: &mut Engine) -> Result<String, String> {
+    unary(s, float::tan)
+}
+
+// y x atan2
+pub fn atan2(s: &mut Engine) -> Result<String, String> {
+    binary(s, float::atan2)
+}
+
+pub fn hypot(s: &mut Engine) -> Result<String, String> {
+    binary(s, float::hypot)
+}
+
+pub fn floor(s: &mut Engine) -> Result<String, String> {
+    rounding(s, float::floor, Decimal::floor)
+}
+
+pub fn ceil(s: &mut Engine) -> Result<String, String> {
+    rounding(s, float::ceil, Decimal::ceil)
+}
+
+pub fn round(s: &mut Engine) -> Result<String, String> {
+    rounding(s, float::round, Decimal::round)
+}
+
+pub fn trunc(s: &mut Engine) -> Result<String, String> {
+    rounding(s, float::trunc, Decimal::trunc)
+}
+
+pub fn pi(s: &mut Engine) -> Result<String, String> {
+    s.main_stack.push(Types::Double(core::f64::consts::PI));
+    Ok("".to_string())
+}
+
+pub fn e(s: &mut Engine) -> Result<String, String> {
+    s.main_stack.push(Types::Double(core::f64::consts::E));
+    Ok("".to_string())
+}
@@ -0,0 +1,224 @@
+use core::cmp::Ordering;
+
+use super::{logic_ops::compare, variable_ops::variable};
+use crate::{
+    errors::{
+        INDEX_OUT_OF_RANGE_ERROR, INVALID_TYPE_ERROR, LIST_SYNTAX_ERROR, STACK_UNDERFLOW_ERROR,
+    },
+    prelude::{Engine, Int, Int128, Types},
+};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(feature = "bigint")]
+use num_traits::ToPrimitive;
+
+// Position in a list or variable given as any integer, negative ones fail
+// with `out_of_range`
+pub fn position(val: Types, out_of_range: &str) -> Result<usize, String> {
+    let val = match val {
+        Types::Byte(a) => a as Int128,
+        Types::Int(a) => a as Int128,
+        Types::UInt(a) => a as Int128,
+        Types::Long(a) => a as Int128,
+        Types::ULong(a) => a as Int128,
+        Types::Int128(a) => a,
+        #[cfg(feature = "bigint")]
+        Types::BigInt(a) => a.to_i128().unwrap_or(-1),
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    };
+
+    usize::try_from(val).map_err(|_| out_of_range.to_string())
+}
+
+// { records the depth of the stack ...
+pub fn list_start(s: &mut Engine) -> Result<String, String> {
+    s.list_stack.push(s.main_stack.len());
+    Ok("".to_string())
+}
+
+// ... and } collects the values produced since then into a list
+pub fn list_end(s: &mut Engine) -> Result<String, String> {
+    let depth = s.list_stack.pop().ok_or(LIST_SYNTAX_ERROR.to_string())?;
+    if s.main_stack.len() < depth {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    let values = s.main_stack.split_off(depth);
+    s.main_stack.push(Types::List(values));
+    Ok("".to_string())
+}
+
+// ( list i -- val )
+pub fn index(s: &mut Engine) -> Result<String, String> {
+    let i = s.main_stack.pop();
+    let list = s.main_stack.pop();
+
+    if i.is_none() || list.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    match (list.unwrap(), i.unwrap()) {
+        (Types::List(list), i) => {
+            let val = list
+                .into_iter()
+                .nth(position(i, INDEX_OUT_OF_RANGE_ERROR)?)
+                .ok_or(INDEX_OUT_OF_RANGE_ERROR.to_string())?;
+            s.main_stack.push(val);
+        }
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    }
+
+    Ok("".to_string())
+}
+
+// ( list val -- list )
+pub fn append(s: &mut Engine) -> Result<String, String> {
+    let val = s.main_stack.pop();
+    let list = s.main_stack.pop();
+
+    if val.is_none() || list.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    match (list.unwrap(), val.unwrap()) {
+        (Types::List(mut list), val) => {
+            list.push(val);
+            s.main_stack.push(Types::List(list));
+        }
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    }
+
+    Ok("".to_string())
+}
+
+// ( list start end -- list ) the values from start up to but without end
+pub fn slice(s: &mut Engine) -> Result<String, String> {
+    let end = s.main_stack.pop();
+    let start = s.main_stack.pop();
+    let list = s.main_stack.pop();
+
+    if end.is_none() || start.is_none() || list.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    match (list.unwrap(), start.unwrap(), end.unwrap()) {
+        (Types::List(list), start, end) => {
+            let values = list
+                .get(
+                    position(start, INDEX_OUT_OF_RANGE_ERROR)?
+                        ..position(end, INDEX_OUT_OF_RANGE_ERROR)?,
+                )
+                .ok_or(INDEX_OUT_OF_RANGE_ERROR.to_string())?;
+            s.main_stack.push(Types::List(values.to_vec()));
+        }
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    }
+
+    Ok("".to_string())
+}
+
+// Pops a list and pushes what `f` makes of it
+fn list_op(s: &mut Engine, f: fn(Vec<Types>) -> Result<Types, String>) -> Result<String, String> {
+    let list = s.main_stack.pop();
+
+    if list.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    match list.unwrap() {
+        Types::List(list) => s.main_stack.push(f(list)?),
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    }
+
+    Ok("".to_string())
+}
+
+pub fn reverse(s: &mut Engine) -> Result<String, String> {
+    list_op(s, |mut list| {
+        list.reverse();
+        Ok(Types::List(list))
+    })
+}
+
+// Stable merge sort that stops at the first pair of values that can't be
+// ordered, `sort_by` needs a total order which NaN and mixed types don't have
+fn merge_sort(mut list: Vec<Types>) -> Result<Vec<Types>, String> {
+    if list.len() < 2 {
+        return Ok(list);
+    }
+
+    let right = merge_sort(list.split_off(list.len() / 2))?;
+    let left = merge_sort(list)?;
+
+    let mut sorted = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+        let next = match compare(a, b)? {
+            Some(Ordering::Greater) => right.next(),
+            Some(_) => left.next(),
+            None => return Err(INVALID_TYPE_ERROR.to_string()),
+        };
+        sorted.extend(next);
+    }
+    sorted.extend(left);
+    sorted.extend(right);
+
+    Ok(sorted)
+}
+
+// Sorts in ascending order like `<` and `>` compare, values that can't be
+// ordered, like NaN, fail
+pub fn sort(s: &mut Engine) -> Result<String, String> {
+    list_op(s, |list| Ok(Types::List(merge_sort(list)?)))
+}
+
+// Number of values in a list or entries in a map
+pub fn length(s: &mut Engine) -> Result<String, String> {
+    let val = s.main_stack.pop();
+
+    if val.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    match val.unwrap() {
+        Types::List(list) => s.main_stack.push(Types::Int(list.len() as Int)),
+        Types::Map(map) => s.main_stack.push(Types::Int(map.len() as Int)),
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    }
+
+    Ok("".to_string())
+}
+
+// ( var -- list ) a copy of the values of a variable
+pub fn var_to_list(s: &mut Engine) -> Result<String, String> {
+    let var = s.main_stack.pop();
+
+    if var.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    let values = variable(s, var.unwrap())?.to_vec();
+    s.main_stack.push(Types::List(values));
+
+    Ok("".to_string())
+}
+
+// ( list var -- ) replaces the values of a variable
+pub fn list_to_var(s: &mut Engine) -> Result<String, String> {
+    let var = s.main_stack.pop();
+    let list = s.main_stack.pop();
+
+    if var.is_none() || list.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    match list.unwrap() {
+        Types::List(list) => *variable(s, var.unwrap())? = list.into(),
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    }
+
+    Ok("".to_string())
+}
@@ -1,62 +1,79 @@
+use core::cmp::Ordering;
+
 use crate::{
-    errors::{INVALID_TYPE_ERROR, STACK_UNDERFLOW_ERROR},
-    prelude::{Double, Engine, Float, Int, Long, Types},
+    errors::{INVALID_TYPE_ERROR, NOT_A_FLAG_ERROR, STACK_UNDERFLOW_ERROR},
+    prelude::{Engine, Promoted, Types},
 };
-
-pub fn equal(s: &mut Engine) -> Result<String, String> {
-    let a = s.main_stack.pop();
-    let b = s.main_stack.pop();
-
-    if a.is_none() || b.is_none() {
-        return Err(STACK_UNDERFLOW_ERROR.to_string());
-    }
-
-    let result: bool = match (a.unwrap(), b.unwrap()) {
-        (Types::Int(a), Types::Int(b)) => a == b,
-        (Types::Int(a), Types::Long(b)) => a as Long == b,
-        (Types::Int(a), Types::Float(b)) => a as Float == b,
-        (Types::Int(a), Types::Double(b)) => a as Double == b,
-        (Types::Int(a), Types::Byte(b)) => a == b as Int,
-
-        (Types::Long(a), Types::Int(b)) => a == b as Long,
-        (Types::Long(a), Types::Long(b)) => a == b,
-        (Types::Long(a), Types::Float(b)) => a as Float == b,
-        (Types::Long(a), Types::Double(b)) => a as Double == b,
-        (Types::Long(a), Types::Byte(b)) => a == b as Long,
-
-        (Types::Float(a), Types::Int(b)) => a == b as Float,
-        (Types::Float(a), Types::Long(b)) => a == b as Float,
-        (Types::Float(a), Types::Float(b)) => a == b,
-        (Types::Float(a), Types::Double(b)) => a as Double == b,
-        (Types::Float(a), Types::Byte(b)) => a == b as Float,
-
-        (Types::Double(a), Types::Int(b)) => a == b as Double,
-        (Types::Double(a), Types::Long(b)) => a == b as Double,
-        (Types::Double(a), Types::Float(b)) => a == b as Double,
-        (Types::Double(a), Types::Double(b)) => a == b,
-        (Types::Double(a), Types::Byte(b)) => a == b as Double,
-
-        (Types::Byte(a), Types::Int(b)) => a as Int == b,
-        (Types::Byte(a), Types::Long(b)) => a as Long == b,
-        (Types::Byte(a), Types::Float(b)) => a as Float == b,
-        (Types::Byte(a), Types::Double(b)) => a as Double == b,
-        (Types::Byte(a), Types::Byte(b)) => a == b,
-
-        (Types::Str(a), Types::Str(b)) => a == b,
-        (Types::Str(_), _) => return Err(INVALID_TYPE_ERROR.to_string()),
-        (_, Types::Str(_)) => return Err(INVALID_TYPE_ERROR.to_string()),
-    };
-
-    if result {
-        s.main_stack.push(Types::Int(-1));
-    } else {
-        s.main_stack.push(Types::Int(0));
+use alloc::string::{String, ToString};
+
+// Compares two values, numbers are compared on their common type of the
+// numeric tower (see types.rs) and lists element by element. Maps, structs and
+// variable handles are only equal or unordered. `None` if one of them is NaN.
+pub fn compare(a: &Types, b: &Types) -> Result<Option<Ordering>, String> {
+    match (a, b) {
+        (Types::Str(a), Types::Str(b)) => return Ok(Some(a.cmp(b))),
+        (Types::Bool(a), Types::Bool(b)) => return Ok(Some(a.cmp(b))),
+        (Types::Char(a), Types::Char(b)) => return Ok(Some(a.cmp(b))),
+        (Types::List(a), Types::List(b)) => {
+            for (a, b) in a.iter().zip(b.iter()) {
+                match compare(a, b)? {
+                    Some(Ordering::Equal) => {}
+                    ordering => return Ok(ordering),
+                }
+            }
+            return Ok(Some(a.len().cmp(&b.len())));
+        }
+        (Types::Map(a), Types::Map(b)) => {
+            if a.len() != b.len() {
+                return Ok(None);
+            }
+            for ((a_key, a), (b_key, b)) in a.iter().zip(b.iter()) {
+                if a_key != b_key || compare(a, b)? != Some(Ordering::Equal) {
+                    return Ok(None);
+                }
+            }
+            return Ok(Some(Ordering::Equal));
+        }
+        (Types::VarRef(a), Types::VarRef(b)) => {
+            return Ok((a == b).then_some(Ordering::Equal));
+        }
+        (Types::Struct(a), Types::Struct(b)) => {
+            if a.name != b.name || a.fields.len() != b.fields.len() {
+                return Ok(None);
+            }
+            for ((a_field, a), (b_field, b)) in a.fields.iter().zip(b.fields.iter()) {
+                if a_field != b_field || compare(a, b)? != Some(Ordering::Equal) {
+                    return Ok(None);
+                }
+            }
+            return Ok(Some(Ordering::Equal));
+        }
+        _ => {}
     }
 
-    Ok("".to_string())
+    Ok(
+        match Types::promote(a, b).ok_or(INVALID_TYPE_ERROR.to_string())? {
+            Promoted::Byte(a, b) => a.partial_cmp(&b),
+            Promoted::Int(a, b) => a.partial_cmp(&b),
+            Promoted::UInt(a, b) => a.partial_cmp(&b),
+            Promoted::Long(a, b) => a.partial_cmp(&b),
+            Promoted::ULong(a, b) => a.partial_cmp(&b),
+            Promoted::Int128(a, b) => a.partial_cmp(&b),
+            #[cfg(feature = "bigint")]
+            Promoted::BigInt(a, b) => a.partial_cmp(&b),
+            Promoted::Decimal(a, b) => a.partial_cmp(&b),
+            Promoted::Float(a, b) => a.partial_cmp(&b),
+            Promoted::Double(a, b) => a.partial_cmp(&b),
+        },
+    )
 }
 
-pub fn not_equal(s: &mut Engine) -> Result<String, String> {
+// Pops the top value and the one below it and pushes the Bool `test` returns
+// for their ordering
+fn comparison(
+    s: &mut Engine,
+    test: fn(top: &Types, below: &Types) -> Result<bool, String>,
+) -> Result<String, String> {
     let a = s.main_stack.pop();
     let b = s.main_stack.pop();
 
@@ -64,49 +81,33 @@ pub fn not_equal(s: &mut Engine) -> Result<String, String> {
         return Err(STACK_UNDERFLOW_ERROR.to_string());
     }
 
-    let result: bool = match (a.unwrap(), b.unwrap()) {
-        (Types::Int(a), Types::Int(b)) => a != b,
-        (Types::Int(a), Types::Long(b)) => a as Long != b,
-        (Types::Int(a), Types::Float(b)) => a as Float != b,
-        (Types::Int(a), Types::Double(b)) => a as Double != b,
-        (Types::Int(a), Types::Byte(b)) => a != b as Int,
+    let result = test(&a.unwrap(), &b.unwrap())?;
+    s.main_stack.push(Types::Bool(result));
 
-        (Types::Long(a), Types::Int(b)) => a != b as Long,
-        (Types::Long(a), Types::Long(b)) => a != b,
-        (Types::Long(a), Types::Float(b)) => a as Float != b,
-        (Types::Long(a), Types::Double(b)) => a as Double != b,
-        (Types::Long(a), Types::Byte(b)) => a != b as Long,
-
-        (Types::Float(a), Types::Int(b)) => a != b as Float,
-        (Types::Float(a), Types::Long(b)) => a != b as Float,
-        (Types::Float(a), Types::Float(b)) => a != b,
-        (Types::Float(a), Types::Double(b)) => a as Double != b,
-        (Types::Float(a), Types::Byte(b)) => a != b as Float,
+    Ok("".to_string())
+}
 
-        (Types::Double(a), Types::Int(b)) => a != b as Double,
-        (Types::Double(a), Types::Long(b)) => a != b as Double,
-        (Types::Double(a), Types::Float(b)) => a != b as Double,
-        (Types::Double(a), Types::Double(b)) => a != b,
-        (Types::Double(a), Types::Byte(b)) => a != b as Double,
+// Truth value of a flag, see `Types::truthy`
+fn flag(val: &Types) -> Result<bool, String> {
+    val.truthy().ok_or(NOT_A_FLAG_ERROR.to_string())
+}
 
-        (Types::Byte(a), Types::Int(b)) => a as Int != b,
-        (Types::Byte(a), Types::Long(b)) => a as Long != b,
-        (Types::Byte(a), Types::Float(b)) => a as Float != b,
-        (Types::Byte(a), Types::Double(b)) => a as Double != b,
-        (Types::Byte(a), Types::Byte(b)) => a != b,
+pub fn true_word(s: &mut Engine) -> Result<String, String> {
+    s.main_stack.push(Types::Bool(true));
+    Ok("".to_string())
+}
 
-        (Types::Str(a), Types::Str(b)) => a != b,
-        (Types::Str(_), _) => return Err(INVALID_TYPE_ERROR.to_string()),
-        (_, Types::Str(_)) => return Err(INVALID_TYPE_ERROR.to_string()),
-    };
+pub fn false_word(s: &mut Engine) -> Result<String, String> {
+    s.main_stack.push(Types::Bool(false));
+    Ok("".to_string())
+}
 
-    if result {
-        s.main_stack.push(Types::Int(-1));
-    } else {
-        s.main_stack.push(Types::Int(0));
-    }
+pub fn equal(s: &mut Engine) -> Result<String, String> {
+    comparison(s, |a, b| Ok(compare(a, b)? == Some(Ordering::Equal)))
+}
 
-    Ok("".to_string())
+pub fn not_equal(s: &mut Engine) -> Result<String, String> {
+    comparison(s, |a, b| Ok(compare(a, b)? != Some(Ordering::Equal)))
 }
 
 pub fn and(s: &mut Engine) -> Result<String, String> {
@@ -117,17 +118,10 @@ pub fn and(s: &mut Engine) -> Result<String, String> {
         return Err(STACK_UNDERFLOW_ERROR.to_string());
     }
 
-    let result: bool = match (a.unwrap(), b.unwrap()) {
-        (Types::Int(a), Types::Int(b)) => a == b,
-
-        (_, _) => return Err(INVALID_TYPE_ERROR.to_string()),
-    };
-
-    if result {
-        s.main_stack.push(Types::Int(-1));
-    } else {
-        s.main_stack.push(Types::Int(0));
-    }
+    // Both values are checked even if the first one decides the result
+    let (a, b) = (flag(&a.unwrap())?, flag(&b.unwrap())?);
+    let result = a && b;
+    s.main_stack.push(Types::Bool(result));
 
     Ok("".to_string())
 }
@@ -140,17 +134,9 @@ pub fn or(s: &mut Engine) -> Result<String, String> {
         return Err(STACK_UNDERFLOW_ERROR.to_string());
     }
 
-    let result: bool = match (a.unwrap(), b.unwrap()) {
-        (Types::Int(a), Types::Int(b)) => (a == -1) || (b == -1),
-
-        (_, _) => return Err(INVALID_TYPE_ERROR.to_string()),
-    };
-
-    if result {
-        s.main_stack.push(Types::Int(-1));
-    } else {
-        s.main_stack.push(Types::Int(0));
-    }
+    let (a, b) = (flag(&a.unwrap())?, flag(&b.unwrap())?);
+    let result = a || b;
+    s.main_stack.push(Types::Bool(result));
 
     Ok("".to_string())
 }
@@ -162,129 +148,17 @@ pub fn not(s: &mut Engine) -> Result<String, String> {
         return Err(STACK_UNDERFLOW_ERROR.to_string());
     }
 
-    let result: bool = match a.unwrap() {
-        Types::Int(a) => {
-            if a == -1 {
-                false
-            } else {
-                true
-            }
-        }
-
-        _ => return Err(INVALID_TYPE_ERROR.to_string()),
-    };
-
-    if result {
-        s.main_stack.push(Types::Int(-1));
-    } else {
-        s.main_stack.push(Types::Int(0));
-    }
+    let result = !flag(&a.unwrap())?;
+    s.main_stack.push(Types::Bool(result));
 
     Ok("".to_string())
 }
 
+// Note that the top of the stack is on the left: `2 3 >` is true
 pub fn grater_than(s: &mut Engine) -> Result<String, String> {
-    let a = s.main_stack.pop();
-    let b = s.main_stack.pop();
-
-    if a.is_none() || b.is_none() {
-        return Err(STACK_UNDERFLOW_ERROR.to_string());
-    }
-
-    let result: bool = match (a.unwrap(), b.unwrap()) {
-        (Types::Int(a), Types::Int(b)) => a > b,
-        (Types::Int(a), Types::Long(b)) => a as Long > b,
-        (Types::Int(a), Types::Float(b)) => a as Float > b,
-        (Types::Int(a), Types::Double(b)) => a as Double > b,
-        (Types::Int(a), Types::Byte(b)) => a > b as Int,
-
-        (Types::Long(a), Types::Int(b)) => a > b as Long,
-        (Types::Long(a), Types::Long(b)) => a > b,
-        (Types::Long(a), Types::Float(b)) => a as Float > b,
-        (Types::Long(a), Types::Double(b)) => a as Double > b,
-        (Types::Long(a), Types::Byte(b)) => a > b as Long,
-
-        (Types::Float(a), Types::Int(b)) => a > b as Float,
-        (Types::Float(a), Types::Long(b)) => a > b as Float,
-        (Types::Float(a), Types::Float(b)) => a > b,
-        (Types::Float(a), Types::Double(b)) => a as Double > b,
-        (Types::Float(a), Types::Byte(b)) => a > b as Float,
-
-        (Types::Double(a), Types::Int(b)) => a > b as Double,
-        (Types::Double(a), Types::Long(b)) => a > b as Double,
-        (Types::Double(a), Types::Float(b)) => a > b as Double,
-        (Types::Double(a), Types::Double(b)) => a > b,
-        (Types::Double(a), Types::Byte(b)) => a > b as Double,
-
-        (Types::Byte(a), Types::Int(b)) => a as Int > b,
-        (Types::Byte(a), Types::Long(b)) => a as Long > b,
-        (Types::Byte(a), Types::Float(b)) => a as Float > b,
-        (Types::Byte(a), Types::Double(b)) => a as Double > b,
-        (Types::Byte(a), Types::Byte(b)) => a > b,
-
-        (Types::Str(a), Types::Str(b)) => a > b,
-        (Types::Str(_), _) => return Err(INVALID_TYPE_ERROR.to_string()),
-        (_, Types::Str(_)) => return Err(INVALID_TYPE_ERROR.to_string()),
-    };
-
-    if result {
-        s.main_stack.push(Types::Int(-1));
-    } else {
-        s.main_stack.push(Types::Int(0));
-    }
-
-    Ok("".to_string())
+    comparison(s, |a, b| Ok(compare(a, b)? == Some(Ordering::Greater)))
 }
 
 pub fn less_than(s: &mut Engine) -> Result<String, String> {
-    let a = s.main_stack.pop();
-    let b = s.main_stack.pop();
-
-    if a.is_none() || b.is_none() {
-        return Err(STACK_UNDERFLOW_ERROR.to_string());
-    }
-
-    let result: bool = match (b.unwrap(), a.unwrap()) {
-        (Types::Int(a), Types::Int(b)) => a > b,
-        (Types::Int(a), Types::Long(b)) => a as Long > b,
-        (Types::Int(a), Types::Float(b)) => a as Float > b,
-        (Types::Int(a), Types::Double(b)) => a as Double > b,
-        (Types::Int(a), Types::Byte(b)) => a > b as Int,
-
-        (Types::Long(a), Types::Int(b)) => a > b as Long,
-        (Types::Long(a), Types::Long(b)) => a > b,
-        (Types::Long(a), Types::Float(b)) => a as Float > b,
-        (Types::Long(a), Types::Double(b)) => a as Double > b,
-        (Types::Long(a), Types::Byte(b)) => a > b as Long,
-
-        (Types::Float(a), Types::Int(b)) => a > b as Float,
-        (Types::Float(a), Types::Long(b)) => a > b as Float,
-        (Types::Float(a), Types::Float(b)) => a > b,
-        (Types::Float(a), Types::Double(b)) => a as Double > b,
-        (Types::Float(a), Types::Byte(b)) => a > b as Float,
-
-        (Types::Double(a), Types::Int(b)) => a > b as Double,
-        (Types::Double(a), Types::Long(b)) => a > b as Double,
-        (Types::Double(a), Types::Float(b)) => a > b as Double,
-        (Types::Double(a), Types::Double(b)) => a > b,
-        (Types::Double(a), Types::Byte(b)) => a > b as Double,
-
-        (Types::Byte(a), Types::Int(b)) => a as Int > b,
-        (Types::Byte(a), Types::Long(b)) => a as Long > b,
-        (Types::Byte(a), Types::Float(b)) => a as Float > b,
-        (Types::Byte(a), Types::Double(b)) => a as Double > b,
-        (Types::Byte(a), Types::Byte(b)) => a > b,
-
-        (Types::Str(a), Types::Str(b)) => a > b,
-        (Types::Str(_), _) => return Err(INVALID_TYPE_ERROR.to_string()),
-        (_, Types::Str(_)) => return Err(INVALID_TYPE_ERROR.to_string()),
-    };
-
-    if result {
-        s.main_stack.push(Types::Int(-1));
-    } else {
-        s.main_stack.push(Types::Int(0));
-    }
-
-    Ok("".to_string())
+    comparison(s, |a, b| Ok(compare(b, a)? == Some(Ordering::Greater)))
 }
@@ -1,7 +1,23 @@
 use crate::{
-    errors::INVALID_TYPE_ERROR,
+    errors::{
+        INVALID_TYPE_ERROR, LOOP_INCREMENT_ERROR, LOOP_STACK_UNDERFLOW_ERROR, NOT_A_FLAG_ERROR,
+        STACK_UNDERFLOW_ERROR, UNBALANCED_LOOP_ERROR,
+    },
     prelude::{Engine, Types},
 };
+use alloc::string::{String, ToString};
+
+// Moves to the next (previous) word while looking for the other end of a
+// loop, fails if the line or word definition ends first
+pub fn move_word(s: &mut Engine, step: i32) -> Result<(), String> {
+    let idx = s.curr_word_idx.last_mut().unwrap();
+    *idx += step;
+
+    if *idx < 0 || *idx >= s.curr_line_vec.last().unwrap().len() as i32 {
+        return Err(UNBALANCED_LOOP_ERROR.to_string());
+    }
+    Ok(())
+}
 
 pub fn while_word(_s: &mut Engine) -> Result<String, String> {
     Ok("".to_string())
@@ -10,25 +26,24 @@ pub fn while_word(_s: &mut Engine) -> Result<String, String> {
 pub fn do_word(s: &mut Engine) -> Result<String, String> {
     let cond = s.main_stack.pop();
     if cond.is_none() {
-        return Err("Error Int stack underflow!".to_string());
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
     }
 
-    match cond.unwrap() {
-        Types::Int(x) => {
-            if x == 0 {
-                let mut controll = 1;
-                while controll != 0 {
-                    *s.curr_word_idx.last_mut().unwrap() += 1;
-
-                    match s.get_curr_word().as_str() {
-                        "while" => controll += 1,
-                        "again" => controll -= 1,
-                        _ => {}
-                    }
+    match cond.unwrap().truthy() {
+        Some(true) => {}
+        Some(false) => {
+            let mut controll = 1;
+            while controll != 0 {
+                move_word(s, 1)?;
+
+                match s.get_curr_word().as_str() {
+                    "while" => controll += 1,
+                    "again" => controll -= 1,
+                    _ => {}
                 }
             }
         }
-        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+        None => return Err(NOT_A_FLAG_ERROR.to_string()),
     }
 
     Ok("".to_string())
@@ -37,7 +52,7 @@ pub fn do_word(s: &mut Engine) -> Result<String, String> {
 pub fn again_word(s: &mut Engine) -> Result<String, String> {
     let mut controll = 1;
     while controll > 0 {
-        *s.curr_word_idx.last_mut().unwrap() -= 1;
+        move_word(s, -1)?;
 
         match s.get_curr_word().as_str() {
             "while" => controll -= 1,
@@ -52,27 +67,23 @@ pub fn again_word(s: &mut Engine) -> Result<String, String> {
 pub fn for_word(s: &mut Engine) -> Result<String, String> {
     let index = s.main_stack.pop();
     if index.is_none() {
-        return Err("Error Int stack underflow!".to_string());
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
     }
 
     let limit = s.main_stack.pop();
     if limit.is_none() {
-        return Err("Error Int stack underflow!".to_string());
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
     }
 
-    let index_int: i32;
-
-    match index.unwrap() {
-        Types::Int(x) => index_int = x,
+    let index_int: i32 = match index.unwrap() {
+        Types::Int(x) => x,
         _ => return Err(INVALID_TYPE_ERROR.to_string()),
-    }
-
-    let limit_int: i32;
+    };
 
-    match limit.unwrap() {
-        Types::Int(x) => limit_int = x,
+    let limit_int: i32 = match limit.unwrap() {
+        Types::Int(x) => x,
         _ => return Err(INVALID_TYPE_ERROR.to_string()),
-    }
+    };
 
     s.loop_stack.push((limit_int, index_int));
 
@@ -82,7 +93,7 @@ pub fn for_word(s: &mut Engine) -> Result<String, String> {
 pub fn next_word(s: &mut Engine) -> Result<String, String> {
     let curr_loop = s.loop_stack.pop();
     if curr_loop.is_none() {
-        return Err("Error Loop control stack underflow!".to_string());
+        return Err(LOOP_STACK_UNDERFLOW_ERROR.to_string());
     }
 
     let mut curr_loop_contents = curr_loop.unwrap();
@@ -91,7 +102,7 @@ pub fn next_word(s: &mut Engine) -> Result<String, String> {
     if curr_loop_contents.1 < curr_loop_contents.0 {
         let mut cntr = 1;
         while cntr != 0 {
-            *s.curr_word_idx.last_mut().unwrap() -= 1;
+            move_word(s, -1)?;
 
             if s.get_curr_word() == "next" || s.get_curr_word() == "bynext" {
                 cntr += 1;
@@ -111,29 +122,29 @@ pub fn bynext_word(s: &mut Engine) -> Result<String, String> {
     let curr_loop = s.loop_stack.pop();
     match curr_loop {
         Some(_) => {}
-        None => return Err("Error Loop control stack underflow!".to_string()),
+        None => return Err(LOOP_STACK_UNDERFLOW_ERROR.to_string()),
     }
 
     let mut curr_loop_contents = curr_loop.unwrap();
 
     let increment = s.main_stack.pop();
     if increment.is_none() {
-        return Err("Error Int stack underflow!".to_string());
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
     }
 
-    let loop_increment: i32;
-
-    match increment.unwrap() {
-        Types::Int(x) => loop_increment = x,
+    // Loops count up to their limit, any other increment would never end them
+    let loop_increment: i32 = match increment.unwrap() {
+        Types::Int(x) if x > 0 => x,
+        Types::Int(_) => return Err(LOOP_INCREMENT_ERROR.to_string()),
         _ => return Err(INVALID_TYPE_ERROR.to_string()),
-    }
+    };
 
-    curr_loop_contents.1 += loop_increment;
+    curr_loop_contents.1 = curr_loop_contents.1.saturating_add(loop_increment);
 
     if curr_loop_contents.1 < curr_loop_contents.0 {
         let mut cntr = 1;
         while cntr != 0 {
-            *s.curr_word_idx.last_mut().unwrap() -= 1;
+            move_word(s, -1)?;
 
             if s.get_curr_word() == "next" || s.get_curr_word() == "bynext" {
                 cntr += 1;
@@ -154,7 +165,7 @@ pub fn i_word(s: &mut Engine) -> Result<String, String> {
 
     match curr_loop {
         Some(_) => {}
-        None => return Err("Error Loop control stack underflow!".to_string()),
+        None => return Err(LOOP_STACK_UNDERFLOW_ERROR.to_string()),
     }
 
     s.main_stack.push(Types::Int(curr_loop.unwrap().1));
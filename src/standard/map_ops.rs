@@ -0,0 +1,204 @@
+use super::loop_ops::move_word;
+use crate::{
+    errors::{INVALID_TYPE_ERROR, LOOP_STACK_UNDERFLOW_ERROR, STACK_UNDERFLOW_ERROR},
+    prelude::{Engine, MapKey, Types},
+};
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+// Strings and integers can be keys
+fn key(val: Types) -> Result<MapKey, String> {
+    MapKey::try_from(val).map_err(|_| INVALID_TYPE_ERROR.to_string())
+}
+
+pub fn map_new(s: &mut Engine) -> Result<String, String> {
+    s.main_stack.push(Types::Map(BTreeMap::new()));
+    Ok("".to_string())
+}
+
+// ( map key val -- map ) replaces the value of a key that is already there
+pub fn map_put(s: &mut Engine) -> Result<String, String> {
+    let val = s.main_stack.pop();
+    let k = s.main_stack.pop();
+    let map = s.main_stack.pop();
+
+    if val.is_none() || k.is_none() || map.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    match map.unwrap() {
+        Types::Map(mut map) => {
+            map.insert(key(k.unwrap())?, val.unwrap());
+            s.main_stack.push(Types::Map(map));
+        }
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    }
+
+    Ok("".to_string())
+}
+
+// ( map key default -- val ) the default if the key isn't in the map
+pub fn map_get(s: &mut Engine) -> Result<String, String> {
+    let default = s.main_stack.pop();
+    let k = s.main_stack.pop();
+    let map = s.main_stack.pop();
+
+    if default.is_none() || k.is_none() || map.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    match map.unwrap() {
+        Types::Map(mut map) => {
+            let val = map.remove(&key(k.unwrap())?);
+            s.main_stack.push(val.unwrap_or(default.unwrap()));
+        }
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    }
+
+    Ok("".to_string())
+}
+
+// ( map key -- bool )
+pub fn map_has(s: &mut Engine) -> Result<String, String> {
+    let k = s.main_stack.pop();
+    let map = s.main_stack.pop();
+
+    if k.is_none() || map.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    match map.unwrap() {
+        Types::Map(map) => {
+            let has = map.contains_key(&key(k.unwrap())?);
+            s.main_stack.push(Types::Bool(has));
+        }
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    }
+
+    Ok("".to_string())
+}
+
+// ( map key -- map ) removing a key that isn't there does nothing
+pub fn map_remove(s: &mut Engine) -> Result<String, String> {
+    let k = s.main_stack.pop();
+    let map = s.main_stack.pop();
+
+    if k.is_none() || map.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    match map.unwrap() {
+        Types::Map(mut map) => {
+            map.remove(&key(k.unwrap())?);
+            s.main_stack.push(Types::Map(map));
+        }
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    }
+
+    Ok("".to_string())
+}
+
+// Pops a map and pushes the list `f` makes of its entries. Keys are in
+// ascending order, integers before strings, so the lists of the keys and of
+// the values of a map line up.
+fn entries(s: &mut Engine, f: fn((MapKey, Types)) -> Types) -> Result<String, String> {
+    let map = s.main_stack.pop();
+
+    if map.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    match map.unwrap() {
+        Types::Map(map) => s
+            .main_stack
+            .push(Types::List(map.into_iter().map(f).collect())),
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    }
+
+    Ok("".to_string())
+}
+
+pub fn map_keys(s: &mut Engine) -> Result<String, String> {
+    entries(s, |(key, _)| key.into())
+}
+
+pub fn map_values(s: &mut Engine) -> Result<String, String> {
+    entries(s, |(_, val)| val)
+}
+
+// Pushes the key and the value of the next entry of the innermost `map-each`,
+// or ends the loop if there are no entries left
+fn next_entry(s: &mut Engine) -> bool {
+    match s.map_loops.last_mut().unwrap().pop() {
+        Some((key, val)) => {
+            s.main_stack.push(key);
+            s.main_stack.push(val);
+            true
+        }
+        None => {
+            s.map_loops.pop();
+            false
+        }
+    }
+}
+
+// ( map -- ) runs the words up to `map-next` with ( key val ) of each entry
+// on the stack, in the order of `map-keys`
+pub fn map_each(s: &mut Engine) -> Result<String, String> {
+    let map = s.main_stack.pop();
+
+    if map.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    let entries: Vec<(Types, Types)> = match map.unwrap() {
+        Types::Map(map) => map
+            .into_iter()
+            .rev()
+            .map(|(key, val)| (key.into(), val))
+            .collect(),
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    };
+
+    s.map_loops.push(entries);
+    if !next_entry(s) {
+        // An empty map skips the body
+        let mut cntr = 1;
+        while cntr != 0 {
+            move_word(s, 1)?;
+
+            match s.get_curr_word().as_str() {
+                "map-each" => cntr += 1,
+                "map-next" => cntr -= 1,
+                _ => {}
+            }
+        }
+    }
+
+    Ok("".to_string())
+}
+
+pub fn map_next(s: &mut Engine) -> Result<String, String> {
+    if s.map_loops.is_empty() {
+        return Err(LOOP_STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    if next_entry(s) {
+        // Back to the words right after the matching `map-each`
+        let mut cntr = 1;
+        while cntr != 0 {
+            move_word(s, -1)?;
+
+            match s.get_curr_word().as_str() {
+                "map-next" => cntr += 1,
+                "map-each" => cntr -= 1,
+                _ => {}
+            }
+        }
+    }
+
+    Ok("".to_string())
+}
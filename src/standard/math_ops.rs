@@ -1,268 +1,368 @@
 use crate::{
-    errors::{INVALID_TYPE_ERROR, STACK_UNDERFLOW_ERROR},
-    prelude::{Double, Engine, Float, Int, Long, Types},
+    errors::{
+        DECIMAL_SCALE_ERROR, DIVISION_BY_ZERO_ERROR, INVALID_TYPE_ERROR, OVERFLOW_ERROR,
+        STACK_UNDERFLOW_ERROR,
+    },
+    prelude::{Arithmetic, Decimal, Engine, Int128, Promoted, Types},
+};
+use alloc::string::{String, ToString};
+#[cfg(feature = "bigint")]
+use {
+    crate::prelude::BigInt,
+    num_traits::{Signed, Zero},
 };
 
-pub fn add(s: &mut Engine) -> Result<String, String> {
-    let x = s.main_stack.pop();
-    if x.is_none() {
-        return Err(STACK_UNDERFLOW_ERROR.to_string());
-    }
+// Pops the operands of a binary word, the top of the stack is the right one
+fn operands(s: &mut Engine) -> Result<(Types, Types), String> {
+    let b = s.main_stack.pop();
+    let a = s.main_stack.pop();
 
-    let y = s.main_stack.pop();
-    if y.is_none() {
-        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    match (a, b) {
+        (Some(a), Some(b)) => Ok((a, b)),
+        _ => Err(STACK_UNDERFLOW_ERROR.to_string()),
     }
+}
 
-    match (y.unwrap(), x.unwrap()) {
-        (Types::Int(a), Types::Int(b)) => s.main_stack.push(Types::Int(a + b)),
-        (Types::Int(a), Types::Float(b)) => s.main_stack.push(Types::Float(a as Float + b)),
-        (Types::Int(a), Types::Byte(b)) => s.main_stack.push(Types::Int(a + b as Int)),
-        (Types::Int(a), Types::Long(b)) => s.main_stack.push(Types::Long(a as Long + b)),
-        (Types::Int(a), Types::Double(b)) => s.main_stack.push(Types::Double(a as Double + b)),
-
-        (Types::Float(a), Types::Int(b)) => s.main_stack.push(Types::Float(a + b as Float)),
-        (Types::Float(a), Types::Float(b)) => s.main_stack.push(Types::Float(a + b)),
-        (Types::Float(a), Types::Byte(b)) => s.main_stack.push(Types::Float(a + b as Float)),
-        (Types::Float(a), Types::Long(b)) => s.main_stack.push(Types::Float(a + b as Float)),
-        (Types::Float(a), Types::Double(b)) => s.main_stack.push(Types::Double(a as Double + b)),
-
-        (Types::Byte(a), Types::Int(b)) => s.main_stack.push(Types::Int(a as Int + b)),
-        (Types::Byte(a), Types::Float(b)) => s.main_stack.push(Types::Float(a as Float + b)),
-        (Types::Byte(a), Types::Byte(b)) => s.main_stack.push(Types::Byte(a + b)),
-        (Types::Byte(a), Types::Long(b)) => s.main_stack.push(Types::Long(a as Long + b)),
-        (Types::Byte(a), Types::Double(b)) => s.main_stack.push(Types::Double(a as Double + b)),
-
-        (Types::Long(a), Types::Int(b)) => s.main_stack.push(Types::Long(a + b as Long)),
-        (Types::Long(a), Types::Long(b)) => s.main_stack.push(Types::Long(a + b)),
-        (Types::Long(a), Types::Float(b)) => s.main_stack.push(Types::Float(a as Float + b)),
-        (Types::Long(a), Types::Double(b)) => s.main_stack.push(Types::Double(a as Double + b)),
-        (Types::Long(a), Types::Byte(b)) => s.main_stack.push(Types::Long(a + b as Long)),
-
-        (Types::Double(a), Types::Int(b)) => s.main_stack.push(Types::Double(a + b as Double)),
-        (Types::Double(a), Types::Long(b)) => s.main_stack.push(Types::Double(a + b as Double)),
-        (Types::Double(a), Types::Float(b)) => s.main_stack.push(Types::Double(a + b as Double)),
-        (Types::Double(a), Types::Double(b)) => s.main_stack.push(Types::Double(a + b)),
-        (Types::Double(a), Types::Byte(b)) => s.main_stack.push(Types::Double(a + b as Double)),
-
-        (Types::Str(a), Types::Str(b)) => s
-            .main_stack
-            .push(Types::Str(a.trim().to_string() + " " + b.trim())),
-        (Types::Str(_), _) => return Err(INVALID_TYPE_ERROR.to_string()),
-        (_, Types::Str(_)) => return Err(INVALID_TYPE_ERROR.to_string()),
-    }
+// Converts the operands to their common type, see the numeric tower in types.rs
+fn promote(a: &Types, b: &Types) -> Result<Promoted, String> {
+    Types::promote(a, b).ok_or(INVALID_TYPE_ERROR.to_string())
+}
 
-    Ok("".to_string())
+// The result of checked integer math that doesn't fit, an error unless the
+// bigint feature lets it continue as a BigInt
+#[cfg(feature = "bigint")]
+macro_rules! overflow {
+    (- $a:expr) => {
+        Types::BigInt(-BigInt::from($a))
+    };
+    ($a:expr, $op:tt, $b:expr) => {
+        Types::BigInt(BigInt::from($a) $op BigInt::from($b))
+    };
 }
 
-pub fn subtract(s: &mut Engine) -> Result<String, String> {
-    let x = s.main_stack.pop();
-    if x.is_none() {
-        return Err(STACK_UNDERFLOW_ERROR.to_string());
-    }
+#[cfg(not(feature = "bigint"))]
+macro_rules! overflow {
+    (- $a:expr) => {
+        return Err(OVERFLOW_ERROR.to_string())
+    };
+    ($a:expr, $op:tt, $b:expr) => {
+        return Err(OVERFLOW_ERROR.to_string())
+    };
+}
 
-    let y = s.main_stack.pop();
-    if y.is_none() {
-        return Err(STACK_UNDERFLOW_ERROR.to_string());
-    }
+// Applies an integer operation the way the arithmetic mode of the engine says
+macro_rules! int_op {
+    ($mode:expr, $ty:ident, $a:expr, $b:expr, $op:tt, $checked:ident, $wrapping:ident, $saturating:ident) => {
+        match $mode {
+            Arithmetic::Checked => match $a.$checked($b) {
+                Some(result) => Types::$ty(result),
+                None => overflow!($a, $op, $b),
+            },
+            Arithmetic::Wrapping => Types::$ty($a.$wrapping($b)),
+            Arithmetic::Saturating => Types::$ty($a.$saturating($b)),
+        }
+    };
+}
+
+// Applies a binary operation to promoted operands, `$op` for floats and big
+// integers and the given methods for fixed width integers. Decimals always use
+// the checked method, there's no wrapping or saturating decimal math.
+macro_rules! binary_op {
+    ($mode:expr, $operands:expr, $op:tt, $checked:ident, $wrapping:ident, $saturating:ident) => {
+        match $operands {
+            Promoted::Byte(a, b) => int_op!($mode, Byte, a, b, $op, $checked, $wrapping, $saturating),
+            Promoted::Int(a, b) => int_op!($mode, Int, a, b, $op, $checked, $wrapping, $saturating),
+            Promoted::Long(a, b) => int_op!($mode, Long, a, b, $op, $checked, $wrapping, $saturating),
+            Promoted::UInt(a, b) => int_op!($mode, UInt, a, b, $op, $checked, $wrapping, $saturating),
+            Promoted::ULong(a, b) => int_op!($mode, ULong, a, b, $op, $checked, $wrapping, $saturating),
+            Promoted::Int128(a, b) => int_op!($mode, Int128, a, b, $op, $checked, $wrapping, $saturating),
+            #[cfg(feature = "bigint")]
+            Promoted::BigInt(a, b) => Types::BigInt(a $op b),
+            Promoted::Decimal(a, b) => Types::Decimal(a.$checked(b).ok_or(OVERFLOW_ERROR.to_string())?),
+            Promoted::Float(a, b) => Types::Float(a $op b),
+            Promoted::Double(a, b) => Types::Double(a $op b),
+        }
+    };
+}
+
+pub fn add(s: &mut Engine) -> Result<String, String> {
+    let (a, b) = operands(s)?;
 
-    match (y.unwrap(), x.unwrap()) {
-        (Types::Int(a), Types::Int(b)) => s.main_stack.push(Types::Int(a - b)),
-        (Types::Int(a), Types::Float(b)) => s.main_stack.push(Types::Float(a as Float - b)),
-        (Types::Int(a), Types::Byte(b)) => s.main_stack.push(Types::Int(a - b as Int)),
-        (Types::Int(a), Types::Long(b)) => s.main_stack.push(Types::Long(a as Long - b)),
-        (Types::Int(a), Types::Double(b)) => s.main_stack.push(Types::Double(a as Double - b)),
-
-        (Types::Float(a), Types::Int(b)) => s.main_stack.push(Types::Float(a - b as Float)),
-        (Types::Float(a), Types::Float(b)) => s.main_stack.push(Types::Float(a - b)),
-        (Types::Float(a), Types::Byte(b)) => s.main_stack.push(Types::Float(a - b as Float)),
-        (Types::Float(a), Types::Long(b)) => s.main_stack.push(Types::Float(a - b as Float)),
-        (Types::Float(a), Types::Double(b)) => s.main_stack.push(Types::Double(a as Double - b)),
-
-        (Types::Byte(a), Types::Int(b)) => s.main_stack.push(Types::Int(a as Int - b)),
-        (Types::Byte(a), Types::Float(b)) => s.main_stack.push(Types::Float(a as Float - b)),
-        (Types::Byte(a), Types::Byte(b)) => s.main_stack.push(Types::Byte(a - b)),
-        (Types::Byte(a), Types::Long(b)) => s.main_stack.push(Types::Long(a as Long - b)),
-        (Types::Byte(a), Types::Double(b)) => s.main_stack.push(Types::Double(a as Double - b)),
-
-        (Types::Long(a), Types::Int(b)) => s.main_stack.push(Types::Long(a - b as Long)),
-        (Types::Long(a), Types::Long(b)) => s.main_stack.push(Types::Long(a - b)),
-        (Types::Long(a), Types::Float(b)) => s.main_stack.push(Types::Float(a as Float - b)),
-        (Types::Long(a), Types::Double(b)) => s.main_stack.push(Types::Double(a as Double - b)),
-        (Types::Long(a), Types::Byte(b)) => s.main_stack.push(Types::Long(a - b as Long)),
-
-        (Types::Double(a), Types::Int(b)) => s.main_stack.push(Types::Double(a - b as Double)),
-        (Types::Double(a), Types::Long(b)) => s.main_stack.push(Types::Double(a - b as Double)),
-        (Types::Double(a), Types::Float(b)) => s.main_stack.push(Types::Double(a - b as Double)),
-        (Types::Double(a), Types::Double(b)) => s.main_stack.push(Types::Double(a - b)),
-        (Types::Double(a), Types::Byte(b)) => s.main_stack.push(Types::Double(a - b as Double)),
-
-        //(Types::Str(a), Types::Str(b)) => s.main_stack.push(Types::Str(a + b.as_str())),
-        (Types::Str(_), _) => return Err(INVALID_TYPE_ERROR.to_string()),
-        (_, Types::Str(_)) => return Err(INVALID_TYPE_ERROR.to_string()),
+    if let (Types::Str(a), Types::Str(b)) = (&a, &b) {
+        s.main_stack
+            .push(Types::Str(a.trim().to_string() + " " + b.trim()));
+        return Ok("".to_string());
     }
 
+    let result = binary_op!(
+        s.arithmetic,
+        promote(&a, &b)?,
+        +,
+        checked_add,
+        wrapping_add,
+        saturating_add
+    );
+    s.main_stack.push(result);
+
+    Ok("".to_string())
+}
+
+pub fn subtract(s: &mut Engine) -> Result<String, String> {
+    let (a, b) = operands(s)?;
+
+    let result = binary_op!(
+        s.arithmetic,
+        promote(&a, &b)?,
+        -,
+        checked_sub,
+        wrapping_sub,
+        saturating_sub
+    );
+    s.main_stack.push(result);
+
     Ok("".to_string())
 }
 
 pub fn multiply(s: &mut Engine) -> Result<String, String> {
-    let x = s.main_stack.pop();
-    let y = s.main_stack.pop();
+    let (a, b) = operands(s)?;
+
+    let result = binary_op!(
+        s.arithmetic,
+        promote(&a, &b)?,
+        *,
+        checked_mul,
+        wrapping_mul,
+        saturating_mul
+    );
+    s.main_stack.push(result);
 
-    if x.is_none() || y.is_none() {
-        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    Ok("".to_string())
+}
+
+// Integer division by zero has no result, floats divide to inf or NaN instead
+fn division_by_zero(operands: &Promoted) -> bool {
+    match operands {
+        Promoted::Byte(_, 0)
+        | Promoted::Int(_, 0)
+        | Promoted::UInt(_, 0)
+        | Promoted::Long(_, 0)
+        | Promoted::ULong(_, 0)
+        | Promoted::Int128(_, 0) => true,
+        #[cfg(feature = "bigint")]
+        Promoted::BigInt(_, b) => b.is_zero(),
+        Promoted::Decimal(_, b) => b.is_zero(),
+        _ => false,
     }
+}
 
-    match (y.unwrap(), x.unwrap()) {
-        (Types::Int(a), Types::Int(b)) => s.main_stack.push(Types::Int(a * b)),
-        (Types::Int(a), Types::Float(b)) => s.main_stack.push(Types::Float(a as Float * b)),
-        (Types::Int(a), Types::Byte(b)) => s.main_stack.push(Types::Int(a * b as Int)),
-        (Types::Int(a), Types::Long(b)) => s.main_stack.push(Types::Long(a as Long * b)),
-        (Types::Int(a), Types::Double(b)) => s.main_stack.push(Types::Double(a as Double * b)),
-
-        (Types::Float(a), Types::Int(b)) => s.main_stack.push(Types::Float(a * b as Float)),
-        (Types::Float(a), Types::Float(b)) => s.main_stack.push(Types::Float(a * b)),
-        (Types::Float(a), Types::Byte(b)) => s.main_stack.push(Types::Float(a * b as Float)),
-        (Types::Float(a), Types::Long(b)) => s.main_stack.push(Types::Float(a * b as Float)),
-        (Types::Float(a), Types::Double(b)) => s.main_stack.push(Types::Double(a as Double * b)),
-
-        (Types::Byte(a), Types::Int(b)) => s.main_stack.push(Types::Int(a as Int * b)),
-        (Types::Byte(a), Types::Float(b)) => s.main_stack.push(Types::Float(a as Float * b)),
-        (Types::Byte(a), Types::Byte(b)) => s.main_stack.push(Types::Byte(a * b)),
-        (Types::Byte(a), Types::Long(b)) => s.main_stack.push(Types::Long(a as Long * b)),
-        (Types::Byte(a), Types::Double(b)) => s.main_stack.push(Types::Double(a as Double * b)),
-
-        (Types::Long(a), Types::Int(b)) => s.main_stack.push(Types::Long(a * b as Long)),
-        (Types::Long(a), Types::Long(b)) => s.main_stack.push(Types::Long(a * b)),
-        (Types::Long(a), Types::Float(b)) => s.main_stack.push(Types::Float(a as Float * b)),
-        (Types::Long(a), Types::Double(b)) => s.main_stack.push(Types::Double(a as Double * b)),
-        (Types::Long(a), Types::Byte(b)) => s.main_stack.push(Types::Long(a * b as Long)),
-
-        (Types::Double(a), Types::Int(b)) => s.main_stack.push(Types::Double(a * b as Double)),
-        (Types::Double(a), Types::Long(b)) => s.main_stack.push(Types::Double(a * b as Double)),
-        (Types::Double(a), Types::Float(b)) => s.main_stack.push(Types::Double(a * b as Double)),
-        (Types::Double(a), Types::Double(b)) => s.main_stack.push(Types::Double(a * b)),
-        (Types::Double(a), Types::Byte(b)) => s.main_stack.push(Types::Double(a * b as Double)),
-
-        //(Types::Str(a), Types::Str(b)) => s.main_stack.push(Types::Str(a + b.as_str())),
-        (Types::Str(_), _) => return Err(INVALID_TYPE_ERROR.to_string()),
-        (_, Types::Str(_)) => return Err(INVALID_TYPE_ERROR.to_string()),
+pub fn divide(s: &mut Engine) -> Result<String, String> {
+    let (a, b) = operands(s)?;
+
+    let operands = promote(&a, &b)?;
+    if division_by_zero(&operands) {
+        return Err(DIVISION_BY_ZERO_ERROR.to_string());
     }
 
+    // Only MIN / -1 overflows
+    let result = binary_op!(
+        s.arithmetic,
+        operands,
+        /,
+        checked_div,
+        wrapping_div,
+        saturating_div
+    );
+    s.main_stack.push(result);
+
     Ok("".to_string())
 }
 
-pub fn divide(s: &mut Engine) -> Result<String, String> {
-    let x = s.main_stack.pop();
-    if x.is_none() {
-        return Err(STACK_UNDERFLOW_ERROR.to_string());
-    }
+pub fn rem_op(s: &mut Engine) -> Result<String, String> {
+    let (a, b) = operands(s)?;
 
-    let y = s.main_stack.pop();
-    if y.is_none() {
-        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    let operands = promote(&a, &b)?;
+    if division_by_zero(&operands) {
+        return Err(DIVISION_BY_ZERO_ERROR.to_string());
     }
 
-    match (y.unwrap(), x.unwrap()) {
-        (Types::Int(a), Types::Int(b)) => s.main_stack.push(Types::Int(a / b)),
-        (Types::Int(a), Types::Float(b)) => s.main_stack.push(Types::Float(a as Float / b)),
-        (Types::Int(a), Types::Byte(b)) => s.main_stack.push(Types::Int(a / b as Int)),
-        (Types::Int(a), Types::Long(b)) => s.main_stack.push(Types::Long(a as Long / b)),
-        (Types::Int(a), Types::Double(b)) => s.main_stack.push(Types::Double(a as Double / b)),
-
-        (Types::Float(a), Types::Int(b)) => s.main_stack.push(Types::Float(a / b as Float)),
-        (Types::Float(a), Types::Float(b)) => s.main_stack.push(Types::Float(a / b)),
-        (Types::Float(a), Types::Byte(b)) => s.main_stack.push(Types::Float(a / b as Float)),
-        (Types::Float(a), Types::Long(b)) => s.main_stack.push(Types::Float(a / b as Float)),
-        (Types::Float(a), Types::Double(b)) => s.main_stack.push(Types::Double(a as Double / b)),
-
-        (Types::Byte(a), Types::Int(b)) => s.main_stack.push(Types::Int(a as Int / b)),
-        (Types::Byte(a), Types::Float(b)) => s.main_stack.push(Types::Float(a as Float / b)),
-        (Types::Byte(a), Types::Byte(b)) => s.main_stack.push(Types::Byte(a / b)),
-        (Types::Byte(a), Types::Long(b)) => s.main_stack.push(Types::Long(a as Long / b)),
-        (Types::Byte(a), Types::Double(b)) => s.main_stack.push(Types::Double(a as Double / b)),
-
-        (Types::Long(a), Types::Int(b)) => s.main_stack.push(Types::Long(a / b as Long)),
-        (Types::Long(a), Types::Long(b)) => s.main_stack.push(Types::Long(a / b)),
-        (Types::Long(a), Types::Float(b)) => s.main_stack.push(Types::Float(a as Float / b)),
-        (Types::Long(a), Types::Double(b)) => s.main_stack.push(Types::Double(a as Double / b)),
-        (Types::Long(a), Types::Byte(b)) => s.main_stack.push(Types::Long(a / b as Long)),
-
-        (Types::Double(a), Types::Int(b)) => s.main_stack.push(Types::Double(a / b as Double)),
-        (Types::Double(a), Types::Long(b)) => s.main_stack.push(Types::Double(a / b as Double)),
-        (Types::Double(a), Types::Float(b)) => s.main_stack.push(Types::Double(a / b as Double)),
-        (Types::Double(a), Types::Double(b)) => s.main_stack.push(Types::Double(a / b)),
-        (Types::Double(a), Types::Byte(b)) => s.main_stack.push(Types::Double(a / b as Double)),
-
-        //(Types::Str(a), Types::Str(b)) => s.main_stack.push(Types::Str(a + b.as_str())),
-        (Types::Str(_), _) => return Err(INVALID_TYPE_ERROR.to_string()),
-        (_, Types::Str(_)) => return Err(INVALID_TYPE_ERROR.to_string()),
-    }
+    // Only MIN % -1 overflows, its wrapped result 0 is also the saturated one
+    let result = binary_op!(
+        s.arithmetic,
+        operands,
+        %,
+        checked_rem,
+        wrapping_rem,
+        wrapping_rem
+    );
+    s.main_stack.push(result);
 
     Ok("".to_string())
 }
 
-pub fn rem_op(s: &mut Engine) -> Result<String, String> {
-    let b = s.main_stack.pop();
+macro_rules! abs_int {
+    ($mode:expr, $ty:ident, $a:expr) => {
+        match $mode {
+            Arithmetic::Checked => match $a.checked_abs() {
+                Some(result) => Types::$ty(result),
+                None => overflow!(-$a),
+            },
+            Arithmetic::Wrapping => Types::$ty($a.wrapping_abs()),
+            Arithmetic::Saturating => Types::$ty($a.saturating_abs()),
+        }
+    };
+}
+
+pub fn abs_op(s: &mut Engine) -> Result<String, String> {
     let a = s.main_stack.pop();
 
-    if a.is_none() || b.is_none() {
+    if a.is_none() {
         return Err(STACK_UNDERFLOW_ERROR.to_string());
     }
 
-    match (a.unwrap(), b.unwrap()) {
-        (Types::Int(a), Types::Int(b)) => s.main_stack.push(Types::Int(a % b)),
-        (Types::Int(a), Types::Float(b)) => s.main_stack.push(Types::Float(a as Float % b)),
-        (Types::Int(a), Types::Byte(b)) => s.main_stack.push(Types::Int(a % b as Int)),
-        (Types::Int(a), Types::Long(b)) => s.main_stack.push(Types::Long(a as Long % b)),
-        (Types::Int(a), Types::Double(b)) => s.main_stack.push(Types::Double(a as Double % b)),
-
-        (Types::Float(a), Types::Int(b)) => s.main_stack.push(Types::Float(a % b as Float)),
-        (Types::Float(a), Types::Float(b)) => s.main_stack.push(Types::Float(a % b)),
-        (Types::Float(a), Types::Byte(b)) => s.main_stack.push(Types::Float(a % b as Float)),
-        (Types::Float(a), Types::Long(b)) => s.main_stack.push(Types::Float(a % b as Float)),
-        (Types::Float(a), Types::Double(b)) => s.main_stack.push(Types::Double(a as Double % b)),
-
-        (Types::Byte(a), Types::Int(b)) => s.main_stack.push(Types::Int(a as Int % b)),
-        (Types::Byte(a), Types::Float(b)) => s.main_stack.push(Types::Float(a as Float % b)),
-        (Types::Byte(a), Types::Byte(b)) => s.main_stack.push(Types::Byte(a % b)),
-        (Types::Byte(a), Types::Long(b)) => s.main_stack.push(Types::Long(a as Long % b)),
-        (Types::Byte(a), Types::Double(b)) => s.main_stack.push(Types::Double(a as Double % b)),
-
-        (Types::Long(a), Types::Int(b)) => s.main_stack.push(Types::Long(a % b as Long)),
-        (Types::Long(a), Types::Long(b)) => s.main_stack.push(Types::Long(a % b)),
-        (Types::Long(a), Types::Float(b)) => s.main_stack.push(Types::Float(a as Float % b)),
-        (Types::Long(a), Types::Double(b)) => s.main_stack.push(Types::Double(a as Double % b)),
-        (Types::Long(a), Types::Byte(b)) => s.main_stack.push(Types::Long(a % b as Long)),
-
-        (Types::Double(a), Types::Int(b)) => s.main_stack.push(Types::Double(a % b as Double)),
-        (Types::Double(a), Types::Long(b)) => s.main_stack.push(Types::Double(a % b as Double)),
-        (Types::Double(a), Types::Float(b)) => s.main_stack.push(Types::Double(a % b as Double)),
-        (Types::Double(a), Types::Double(b)) => s.main_stack.push(Types::Double(a % b)),
-        (Types::Double(a), Types::Byte(b)) => s.main_stack.push(Types::Double(a % b as Double)),
-
-        //(Types::Str(a), Types::Str(b)) => s.main_stack.push(Types::Str(a + b.as_str())),
-        (Types::Str(_), _) => return Err(INVALID_TYPE_ERROR.to_string()),
-        (_, Types::Str(_)) => return Err(INVALID_TYPE_ERROR.to_string()),
-    }
+    // abs(MIN) doesn't fit, unsigned values have no sign to drop
+    let abs = match a.unwrap() {
+        Types::Int(a) => abs_int!(s.arithmetic, Int, a),
+        Types::Long(a) => abs_int!(s.arithmetic, Long, a),
+        Types::Int128(a) => abs_int!(s.arithmetic, Int128, a),
+        a @ (Types::Byte(_) | Types::UInt(_) | Types::ULong(_)) => a,
+        #[cfg(feature = "bigint")]
+        Types::BigInt(a) => Types::BigInt(a.abs()),
+        Types::Decimal(a) => Types::Decimal(a.checked_abs().ok_or(OVERFLOW_ERROR.to_string())?),
+        Types::Float(a) => Types::Float(a.abs()),
+        Types::Double(a) => Types::Double(a.abs()),
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    };
+    s.main_stack.push(abs);
 
     Ok("".to_string())
 }
 
-pub fn abs_op(s: &mut Engine) -> Result<String, String> {
+fn set_arithmetic(s: &mut Engine, arithmetic: Arithmetic) -> Result<String, String> {
+    s.arithmetic = arithmetic;
+    Ok("".to_string())
+}
+
+pub fn checked(s: &mut Engine) -> Result<String, String> {
+    set_arithmetic(s, Arithmetic::Checked)
+}
+
+pub fn wrapping(s: &mut Engine) -> Result<String, String> {
+    set_arithmetic(s, Arithmetic::Wrapping)
+}
+
+pub fn saturating(s: &mut Engine) -> Result<String, String> {
+    set_arithmetic(s, Arithmetic::Saturating)
+}
+
+// Unsigned values can only be negated without overflow if they are 0, so a
+// saturated negation is always 0
+macro_rules! neg_int {
+    ($mode:expr, $ty:ident, $a:expr, $saturating:expr) => {
+        match $mode {
+            Arithmetic::Checked => match $a.checked_neg() {
+                Some(result) => Types::$ty(result),
+                None => overflow!(-$a),
+            },
+            Arithmetic::Wrapping => Types::$ty($a.wrapping_neg()),
+            Arithmetic::Saturating => Types::$ty($saturating),
+        }
+    };
+}
+
+pub fn negate(s: &mut Engine) -> Result<String, String> {
     let a = s.main_stack.pop();
 
     if a.is_none() {
         return Err(STACK_UNDERFLOW_ERROR.to_string());
     }
 
-    match a.unwrap() {
-        Types::Int(a) => s.main_stack.push(Types::Int(a.abs())),
-        Types::Long(a) => s.main_stack.push(Types::Long(a.abs())),
-        Types::Float(a) => s.main_stack.push(Types::Float(a.abs())),
-        Types::Double(a) => s.main_stack.push(Types::Double(a.abs())),
+    let result = match a.unwrap() {
+        Types::Byte(a) => neg_int!(s.arithmetic, Byte, a, 0),
+        Types::Int(a) => neg_int!(s.arithmetic, Int, a, a.saturating_neg()),
+        Types::UInt(a) => neg_int!(s.arithmetic, UInt, a, 0),
+        Types::Long(a) => neg_int!(s.arithmetic, Long, a, a.saturating_neg()),
+        Types::ULong(a) => neg_int!(s.arithmetic, ULong, a, 0),
+        Types::Int128(a) => neg_int!(s.arithmetic, Int128, a, a.saturating_neg()),
+        #[cfg(feature = "bigint")]
+        Types::BigInt(a) => Types::BigInt(-a),
+        Types::Decimal(a) => Types::Decimal(a.checked_neg().ok_or(OVERFLOW_ERROR.to_string())?),
+        Types::Float(a) => Types::Float(-a),
+        Types::Double(a) => Types::Double(-a),
+        Types::Bool(_)
+        | Types::Char(_)
+        | Types::Str(_)
+        | Types::List(_)
+        | Types::Map(_)
+        | Types::Struct(_)
+        | Types::VarRef(_) => return Err(INVALID_TYPE_ERROR.to_string()),
+    };
+    s.main_stack.push(result);
+
+    Ok("".to_string())
+}
+
+// Pushes the smaller or larger of the two operands in their common type, a NaN
+// loses against any number
+fn min_max(s: &mut Engine, max: bool) -> Result<String, String> {
+    let (a, b) = operands(s)?;
+
+    let result = match promote(&a, &b)? {
+        Promoted::Byte(a, b) => Types::Byte(if max { a.max(b) } else { a.min(b) }),
+        Promoted::Int(a, b) => Types::Int(if max { a.max(b) } else { a.min(b) }),
+        Promoted::UInt(a, b) => Types::UInt(if max { a.max(b) } else { a.min(b) }),
+        Promoted::Long(a, b) => Types::Long(if max { a.max(b) } else { a.min(b) }),
+        Promoted::ULong(a, b) => Types::ULong(if max { a.max(b) } else { a.min(b) }),
+        Promoted::Int128(a, b) => Types::Int128(if max { a.max(b) } else { a.min(b) }),
+        #[cfg(feature = "bigint")]
+        Promoted::BigInt(a, b) => Types::BigInt(if max { a.max(b) } else { a.min(b) }),
+        Promoted::Decimal(a, b) => Types::Decimal(if max { a.max(b) } else { a.min(b) }),
+        Promoted::Float(a, b) => Types::Float(if max { a.max(b) } else { a.min(b) }),
+        Promoted::Double(a, b) => Types::Double(if max { a.max(b) } else { a.min(b) }),
+    };
+    s.main_stack.push(result);
+
+    Ok("".to_string())
+}
+
+pub fn min_op(s: &mut Engine) -> Result<String, String> {
+    min_max(s, false)
+}
+
+pub fn max_op(s: &mut Engine) -> Result<String, String> {
+    min_max(s, true)
+}
+
+// <value> <scale> rescale, rounds or pads a number to `scale` digits after the
+// decimal point and gives a Decimal
+pub fn rescale(s: &mut Engine) -> Result<String, String> {
+    let (val, scale) = operands(s)?;
+
+    let scale = match scale {
+        Types::Byte(a) => a as Int128,
+        Types::Int(a) => a as Int128,
+        Types::UInt(a) => a as Int128,
+        Types::Long(a) => a as Int128,
+        Types::ULong(a) => a as Int128,
+        Types::Int128(a) => a,
         _ => return Err(INVALID_TYPE_ERROR.to_string()),
-    }
+    };
+    let scale = u32::try_from(scale)
+        .ok()
+        .filter(|scale| *scale <= Decimal::MAX_SCALE)
+        .ok_or(DECIMAL_SCALE_ERROR.to_string())?;
+
+    let val = match val {
+        Types::Byte(a) => Decimal::from(a as Int128),
+        Types::Int(a) => Decimal::from(a as Int128),
+        Types::UInt(a) => Decimal::from(a as Int128),
+        Types::Long(a) => Decimal::from(a as Int128),
+        Types::ULong(a) => Decimal::from(a as Int128),
+        Types::Int128(a) => Decimal::from(a),
+        Types::Decimal(a) => a,
+        _ => return Err(INVALID_TYPE_ERROR.to_string()),
+    };
+    let rescaled = val.rescale(scale).ok_or(OVERFLOW_ERROR.to_string())?;
+    s.main_stack.push(Types::Decimal(rescaled));
 
     Ok("".to_string())
 }
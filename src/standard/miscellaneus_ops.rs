@@ -1,7 +1,9 @@
+use super::char_ops::code_point;
 use crate::{
-    errors::{INVALID_TYPE_ERROR, STACK_UNDERFLOW_ERROR},
+    errors::{INVALID_TYPE_ERROR, NOT_A_FLAG_ERROR, STACK_UNDERFLOW_ERROR},
     prelude::{Engine, Types},
 };
+use alloc::string::{String, ToString};
 
 pub fn bye(s: &mut Engine) -> Result<String, String> {
     if !s.get_compiled_exec() {
@@ -14,16 +16,8 @@ pub fn silent(s: &mut Engine) -> Result<String, String> {
     let value = s.main_stack.pop();
 
     if let Some(value) = value {
-        if let Types::Int(value) = value {
-            if value == -1 {
-                s.silent = true;
-            } else if value == 0 {
-                s.silent = false
-            }
-
-            return Ok("".to_string());
-        }
-        return Err(INVALID_TYPE_ERROR.to_string());
+        s.silent = value.truthy().ok_or(NOT_A_FLAG_ERROR.to_string())?;
+        return Ok("".to_string());
     }
     Err(STACK_UNDERFLOW_ERROR.to_string())
 }
@@ -52,9 +46,8 @@ pub fn emit(s: &mut Engine) -> Result<String, String> {
         return Err(STACK_UNDERFLOW_ERROR.to_string());
     }
 
-    if let Types::Byte(character) = character.unwrap() {
-        return Ok(String::from(character as char));
-    } else {
-        return Err(INVALID_TYPE_ERROR.to_string());
+    match character.unwrap() {
+        Types::Char(character) => Ok(String::from(character)),
+        code => Ok(String::from(code_point(&code)?)),
     }
 }
@@ -1,48 +1,88 @@
+mod bit_ops;
+mod char_ops;
 mod comment_ops;
 mod conditional_ops;
+mod debug_ops;
+mod effect_ops;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod float_ops;
+mod list_ops;
 mod logic_ops;
 mod loop_ops;
+mod map_ops;
 mod math_ops;
 mod miscellaneus_ops;
 mod stack_ops;
 mod string_ops;
+mod struct_ops;
+mod test_ops;
 mod type_ops;
 mod value_ops;
 mod variable_ops;
 mod word_ops;
 
 use crate::prelude::{Engine, Word, WordList};
+use alloc::{vec, vec::Vec};
 
 use self::{
+    bit_ops::{arshift, band, binvert, bor, bxor, lshift, popcount, rotl, rotr, rshift},
+    char_ops::{char_to_int, chars, int_to_char, is_alpha, is_digit, is_space, lower, upper},
     comment_ops::comment_toggle,
     conditional_ops::{current_cond, else_word, if_word, skip, then_word},
-    logic_ops::{and, equal, grater_than, less_than, not, not_equal, or},
+    debug_ops::{noprofile_word, notrace_word, profile_report_word, profile_word, trace_word},
+    list_ops::{
+        append, index, length, list_end, list_start, list_to_var, reverse, slice, sort, var_to_list,
+    },
+    logic_ops::{and, equal, false_word, grater_than, less_than, not, not_equal, or, true_word},
     loop_ops::{again_word, bynext_word, do_word, for_word, i_word, next_word, while_word},
-    math_ops::{abs_op, add, divide, multiply, rem_op, subtract},
+    map_ops::{
+        map_each, map_get, map_has, map_keys, map_new, map_next, map_put, map_remove, map_values,
+    },
+    math_ops::{
+        abs_op, add, checked, divide, max_op, min_op, multiply, negate, rem_op, rescale,
+        saturating, subtract, wrapping,
+    },
     miscellaneus_ops::{bye, emit, input, nl, silent},
     stack_ops::{dot, drop_word, dup, peek, rot, swap_word, two_dup},
     string_ops::{
         string_concat, string_creation, string_mode_toggle, string_split, string_split_whitespace,
     },
-    type_ops::{to_byte, to_double, to_float, to_int, to_long, to_string},
-    value_ops::{byte_number, double_number, float_number, int_number, long_number},
+    struct_ops::{
+        construct, get_field, instance_of, is_constructor, is_getter, is_predicate, is_setter,
+        set_field, struct_word,
+    },
+    test_ops::{assert_eq_word, assert_word, test_arrow, test_end, test_start},
+    type_ops::{
+        to_byte, to_decimal, to_double, to_float, to_int, to_int128, to_long, to_string, to_uint,
+        to_ulong,
+    },
+    value_ops::{
+        byte_number, char_literal, decimal_number, double_number, float_number, int128_number,
+        int_number, is_byte, is_char, is_decimal, is_double, is_float, is_int, is_int128, is_long,
+        is_uint, is_ulong, long_number, uint_number, ulong_number,
+    },
     variable_ops::{
         get_from_index_word, get_var_addr_word, len_word, let_word, pop_word, push_word,
         set_in_index_word,
     },
-    word_ops::{compile, end_compile, run_compiled, start_compile},
+    word_ops::{compile, end_compile, is_compiled_word, run_compiled, start_compile},
 };
 
-pub struct Standard {
+// Control flow: comments, conditionals and loops.
+//
+// Has to be imported before all other word lists, otherwise words inside of
+// comments and not taken branches will still be executed.
+pub struct Control {
     words: Vec<Word>,
 }
 
-impl WordList for Standard {
+impl WordList for Control {
     fn new() -> Self {
-        Standard {
+        Control {
             words: vec![
                 // Comments
                 (
+                    "(",
                     |s| {
                         (s.get_curr_word() == "(" || s.get_curr_word() == ")")
                             && (s.mode_normal() || s.mode_comment())
@@ -51,152 +91,752 @@ impl WordList for Standard {
                 ),
                 // Conditional words
                 (
+                    "if",
                     |s| s.get_curr_word() == "if" && s.get_compiled_exec() && s.mode_normal(),
                     if_word,
                 ),
                 (
+                    "else",
                     |s| s.get_curr_word() == "else" && s.get_compiled_exec() && s.mode_normal(),
                     else_word,
                 ),
                 (
+                    "then",
                     |s| s.get_curr_word() == "then" && s.get_compiled_exec() && s.mode_normal(),
                     then_word,
                 ),
                 (
+                    "<skip>",
                     |s: &Engine| (1 != current_cond(s) && s.mode_normal()) || s.mode_comment(),
                     skip,
                 ),
                 // Loop words
                 (
+                    "for",
                     |s| s.get_curr_word() == "for" && s.get_compiled_exec() && s.mode_normal(),
                     for_word,
                 ),
                 (
+                    "next",
                     |s| s.get_curr_word() == "next" && s.get_compiled_exec() && s.mode_normal(),
                     next_word,
                 ),
                 (
+                    "bynext",
                     |s| s.get_curr_word() == "bynext" && s.get_compiled_exec() && s.mode_normal(),
                     bynext_word,
                 ),
                 (
+                    "while",
                     |s| s.get_curr_word() == "while" && s.get_compiled_exec() && s.mode_normal(),
                     while_word,
                 ),
                 (
+                    "do",
                     |s| s.get_curr_word() == "do" && s.get_compiled_exec() && s.mode_normal(),
                     do_word,
                 ),
                 (
+                    "again",
                     |s| s.get_curr_word() == "again" && s.get_compiled_exec() && s.mode_normal(),
                     again_word,
                 ),
                 (
+                    "i",
                     |s| s.get_curr_word() == "i" && s.get_compiled_exec() && s.mode_normal(),
                     i_word,
                 ),
-                // Variable words
-                (|s| s.get_curr_word() == "let" && s.mode_normal(), let_word),
+            ],
+        }
+    }
+
+    fn get_words(&self) -> Vec<Word> {
+        self.words.clone()
+    }
+}
+
+// Variable creation and access
+pub struct Variables {
+    words: Vec<Word>,
+}
+
+impl WordList for Variables {
+    fn new() -> Self {
+        Variables {
+            words: vec![
+                (
+                    "let",
+                    |s| s.get_curr_word() == "let" && s.mode_normal(),
+                    let_word,
+                ),
                 (
+                    "@<name>",
                     |s| s.get_curr_word().starts_with("@") && s.mode_normal(),
                     get_var_addr_word,
                 ),
                 (
+                    "push",
                     |s| s.get_curr_word() == "push" && s.mode_normal(),
                     push_word,
                 ),
-                (|s| s.get_curr_word() == "pop" && s.mode_normal(), pop_word),
                 (
+                    "pop",
+                    |s| s.get_curr_word() == "pop" && s.mode_normal(),
+                    pop_word,
+                ),
+                (
+                    "get",
                     |s| s.get_curr_word() == "get" && s.mode_normal(),
                     get_from_index_word,
                 ),
                 (
+                    "set",
                     |s| s.get_curr_word() == "set" && s.mode_normal(),
                     set_in_index_word,
                 ),
-                (|s| s.get_curr_word() == "len" && s.mode_normal(), len_word),
-                // Math operations
-                (|s| s.get_curr_word() == "+" && s.mode_normal(), add),
-                (|s| s.get_curr_word() == "-" && s.mode_normal(), subtract),
-                (|s| s.get_curr_word() == "*" && s.mode_normal(), multiply),
-                (|s| s.get_curr_word() == "/" && s.mode_normal(), divide),
-                (|s| s.get_curr_word() == "%" && s.mode_normal(), rem_op),
-                (|s| s.get_curr_word() == "abs" && s.mode_normal(), abs_op),
-                // Logic operations
-                (|s| s.get_curr_word() == "==" && s.mode_normal(), equal),
-                (|s| s.get_curr_word() == "!=" && s.mode_normal(), not_equal),
-                (|s| s.get_curr_word() == "and" && s.mode_normal(), and),
-                (|s| s.get_curr_word() == "or" && s.mode_normal(), or),
-                (|s| s.get_curr_word() == "not" && s.mode_normal(), not),
-                (|s| s.get_curr_word() == ">" && s.mode_normal(), grater_than),
-                (|s| s.get_curr_word() == "<" && s.mode_normal(), less_than),
+                (
+                    "len",
+                    |s| s.get_curr_word() == "len" && s.mode_normal(),
+                    len_word,
+                ),
+            ],
+        }
+    }
+
+    fn get_words(&self) -> Vec<Word> {
+        self.words.clone()
+    }
+}
+
+// Math operations
+pub struct Math {
+    words: Vec<Word>,
+}
+
+impl WordList for Math {
+    fn new() -> Self {
+        Math {
+            words: vec![
+                ("+", |s| s.get_curr_word() == "+" && s.mode_normal(), add),
+                (
+                    "-",
+                    |s| s.get_curr_word() == "-" && s.mode_normal(),
+                    subtract,
+                ),
+                (
+                    "*",
+                    |s| s.get_curr_word() == "*" && s.mode_normal(),
+                    multiply,
+                ),
+                ("/", |s| s.get_curr_word() == "/" && s.mode_normal(), divide),
+                ("%", |s| s.get_curr_word() == "%" && s.mode_normal(), rem_op),
+                (
+                    "abs",
+                    |s| s.get_curr_word() == "abs" && s.mode_normal(),
+                    abs_op,
+                ),
+                (
+                    "min",
+                    |s| s.get_curr_word() == "min" && s.mode_normal(),
+                    min_op,
+                ),
+                (
+                    "max",
+                    |s| s.get_curr_word() == "max" && s.mode_normal(),
+                    max_op,
+                ),
+                (
+                    "negate",
+                    |s| s.get_curr_word() == "negate" && s.mode_normal(),
+                    negate,
+                ),
+                (
+                    "checked",
+                    |s| s.get_curr_word() == "checked" && s.mode_normal(),
+                    checked,
+                ),
+                (
+                    "wrapping",
+                    |s| s.get_curr_word() == "wrapping" && s.mode_normal(),
+                    wrapping,
+                ),
+                (
+                    "saturating",
+                    |s| s.get_curr_word() == "saturating" && s.mode_normal(),
+                    saturating,
+                ),
+                (
+                    "rescale",
+                    |s| s.get_curr_word() == "rescale" && s.mode_normal(),
+                    rescale,
+                ),
+            ],
+        }
+    }
+
+    fn get_words(&self) -> Vec<Word> {
+        self.words.clone()
+    }
+}
+
+// Floating point functions and constants, needs either the std or the libm
+// feature
+#[cfg(any(feature = "std", feature = "libm"))]
+pub struct ExtendedMath {
+    words: Vec<Word>,
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl WordList for ExtendedMath {
+    fn new() -> Self {
+        ExtendedMath {
+            words: vec![
+                (
+                    "sqrt",
+                    |s| s.get_curr_word() == "sqrt" && s.mode_normal(),
+                    float_ops::sqrt,
+                ),
+                (
+                    "pow",
+                    |s| s.get_curr_word() == "pow" && s.mode_normal(),
+                    float_ops::pow,
+                ),
+                (
+                    "exp",
+                    |s| s.get_curr_word() == "exp" && s.mode_normal(),
+                    float_ops::exp,
+                ),
+                (
+                    "ln",
+                    |s| s.get_curr_word() == "ln" && s.mode_normal(),
+                    float_ops::ln,
+                ),
+                (
+                    "log10",
+                    |s| s.get_curr_word() == "log10" && s.mode_normal(),
+                    float_ops::log10,
+                ),
+                (
+                    "sin",
+                    |s| s.get_curr_word() == "sin" && s.mode_normal(),
+                    float_ops::sin,
+                ),
+                (
+                    "cos",
+                    |s| s.get_curr_word() == "cos" && s.mode_normal(),
+                    float_ops::cos,
+                ),
+                (
+                    "tan",
+                    |s| s.get_curr_word() == "tan" && s.mode_normal(),
+                    float_ops::tan,
+                ),
+                (
+                    "atan2",
+                    |s| s.get_curr_word() == "atan2" && s.mode_normal(),
+                    float_ops::atan2,
+                ),
+                (
+                    "hypot",
+                    |s| s.get_curr_word() == "hypot" && s.mode_normal(),
+                    float_ops::hypot,
+                ),
+                (
+                    "floor",
+                    |s| s.get_curr_word() == "floor" && s.mode_normal(),
+                    float_ops::floor,
+                ),
+                (
+                    "ceil",
+                    |s| s.get_curr_word() == "ceil" && s.mode_normal(),
+                    float_ops::ceil,
+                ),
+                (
+                    "round",
+                    |s| s.get_curr_word() == "round" && s.mode_normal(),
+                    float_ops::round,
+                ),
+                (
+                    "trunc",
+                    |s| s.get_curr_word() == "trunc" && s.mode_normal(),
+                    float_ops::trunc,
+                ),
+                (
+                    "pi",
+                    |s| s.get_curr_word() == "pi" && s.mode_normal(),
+                    float_ops::pi,
+                ),
+                (
+                    "e",
+                    |s| s.get_curr_word() == "e" && s.mode_normal(),
+                    float_ops::e,
+                ),
+            ],
+        }
+    }
+
+    fn get_words(&self) -> Vec<Word> {
+        self.words.clone()
+    }
+}
+
+// Arbitrary precision integers: the `123n` literal and `to_bigint`, needs the
+// bigint feature
+#[cfg(feature = "bigint")]
+pub struct BigIntegers {
+    words: Vec<Word>,
+}
+
+#[cfg(feature = "bigint")]
+impl WordList for BigIntegers {
+    fn new() -> Self {
+        BigIntegers {
+            words: vec![
+                (
+                    "<bigint>",
+                    |s| value_ops::is_bigint(&s.get_curr_word()) && s.mode_normal(),
+                    value_ops::bigint_number,
+                ),
+                (
+                    "to_bigint",
+                    |s| s.get_curr_word() == "to_bigint" && s.mode_normal(),
+                    type_ops::to_bigint,
+                ),
+            ],
+        }
+    }
+
+    fn get_words(&self) -> Vec<Word> {
+        self.words.clone()
+    }
+}
+
+// Logic operations, comparisons and bit manipulation
+pub struct Logic {
+    words: Vec<Word>,
+}
+
+impl WordList for Logic {
+    fn new() -> Self {
+        Logic {
+            words: vec![
+                (
+                    "==",
+                    |s| s.get_curr_word() == "==" && s.mode_normal(),
+                    equal,
+                ),
+                (
+                    "!=",
+                    |s| s.get_curr_word() == "!=" && s.mode_normal(),
+                    not_equal,
+                ),
+                (
+                    "and",
+                    |s| s.get_curr_word() == "and" && s.mode_normal(),
+                    and,
+                ),
+                ("or", |s| s.get_curr_word() == "or" && s.mode_normal(), or),
+                (
+                    "not",
+                    |s| s.get_curr_word() == "not" && s.mode_normal(),
+                    not,
+                ),
+                (
+                    ">",
+                    |s| s.get_curr_word() == ">" && s.mode_normal(),
+                    grater_than,
+                ),
+                (
+                    "<",
+                    |s| s.get_curr_word() == "<" && s.mode_normal(),
+                    less_than,
+                ),
+                (
+                    "true",
+                    |s| s.get_curr_word() == "true" && s.mode_normal(),
+                    true_word,
+                ),
+                (
+                    "false",
+                    |s| s.get_curr_word() == "false" && s.mode_normal(),
+                    false_word,
+                ),
+                // Bit manipulation
+                (
+                    "band",
+                    |s| s.get_curr_word() == "band" && s.mode_normal(),
+                    band,
+                ),
+                (
+                    "bor",
+                    |s| s.get_curr_word() == "bor" && s.mode_normal(),
+                    bor,
+                ),
+                (
+                    "bxor",
+                    |s| s.get_curr_word() == "bxor" && s.mode_normal(),
+                    bxor,
+                ),
+                (
+                    "binvert",
+                    |s| s.get_curr_word() == "binvert" && s.mode_normal(),
+                    binvert,
+                ),
+                (
+                    "lshift",
+                    |s| s.get_curr_word() == "lshift" && s.mode_normal(),
+                    lshift,
+                ),
+                (
+                    "rshift",
+                    |s| s.get_curr_word() == "rshift" && s.mode_normal(),
+                    rshift,
+                ),
+                (
+                    "arshift",
+                    |s| s.get_curr_word() == "arshift" && s.mode_normal(),
+                    arshift,
+                ),
+                (
+                    "popcount",
+                    |s| s.get_curr_word() == "popcount" && s.mode_normal(),
+                    popcount,
+                ),
+                (
+                    "rotl",
+                    |s| s.get_curr_word() == "rotl" && s.mode_normal(),
+                    rotl,
+                ),
+                (
+                    "rotr",
+                    |s| s.get_curr_word() == "rotr" && s.mode_normal(),
+                    rotr,
+                ),
+            ],
+        }
+    }
+
+    fn get_words(&self) -> Vec<Word> {
+        self.words.clone()
+    }
+}
+
+// Stack manipulation and number literals
+pub struct CoreStack {
+    words: Vec<Word>,
+}
+
+impl WordList for CoreStack {
+    fn new() -> Self {
+        CoreStack {
+            words: vec![
                 // Stack operations
-                (|s| s.get_curr_word() == "." && s.mode_normal(), dot),
-                (|s| s.get_curr_word() == "dup" && s.mode_normal(), dup),
-                (|s| s.get_curr_word() == "2dup" && s.mode_normal(), two_dup),
+                (".", |s| s.get_curr_word() == "." && s.mode_normal(), dot),
+                (
+                    "dup",
+                    |s| s.get_curr_word() == "dup" && s.mode_normal(),
+                    dup,
+                ),
                 (
+                    "2dup",
+                    |s| s.get_curr_word() == "2dup" && s.mode_normal(),
+                    two_dup,
+                ),
+                (
+                    "drop",
                     |s| s.get_curr_word() == "drop" && s.mode_normal(),
                     drop_word,
                 ),
                 (
+                    "swap",
                     |s| s.get_curr_word() == "swap" && s.mode_normal(),
                     swap_word,
                 ),
-                (|s| s.get_curr_word() == "rot" && s.mode_normal(), rot),
-                (|s| s.get_curr_word() == "peek" && s.mode_normal(), peek),
                 (
+                    "rot",
+                    |s| s.get_curr_word() == "rot" && s.mode_normal(),
+                    rot,
+                ),
+                (
+                    "peek",
+                    |s| s.get_curr_word() == "peek" && s.mode_normal(),
+                    peek,
+                ),
+                // Read number
+                (
+                    "<int>",
+                    |s| is_int(&s.get_curr_word()) && s.mode_normal(),
+                    int_number,
+                ),
+                (
+                    "<long>",
+                    |s| is_long(&s.get_curr_word()) && s.mode_normal(),
+                    long_number,
+                ),
+                (
+                    "<uint>",
+                    |s| is_uint(&s.get_curr_word()) && s.mode_normal(),
+                    uint_number,
+                ),
+                (
+                    "<ulong>",
+                    |s| is_ulong(&s.get_curr_word()) && s.mode_normal(),
+                    ulong_number,
+                ),
+                (
+                    "<int128>",
+                    |s| is_int128(&s.get_curr_word()) && s.mode_normal(),
+                    int128_number,
+                ),
+                (
+                    "<decimal>",
+                    |s| is_decimal(&s.get_curr_word()) && s.mode_normal(),
+                    decimal_number,
+                ),
+                (
+                    "<float>",
+                    |s| is_float(&s.get_curr_word()) && s.mode_normal(),
+                    float_number,
+                ),
+                (
+                    "<double>",
+                    |s| is_double(&s.get_curr_word()) && s.mode_normal(),
+                    double_number,
+                ),
+                (
+                    "<byte>",
+                    |s| is_byte(&s.get_curr_word()) && s.mode_normal(),
+                    byte_number,
+                ),
+                (
+                    "<char>",
+                    |s| is_char(&s.get_curr_word()) && s.mode_normal(),
+                    char_literal,
+                ),
+            ],
+        }
+    }
+
+    fn get_words(&self) -> Vec<Word> {
+        self.words.clone()
+    }
+}
+
+// Custom word definition and execution
+pub struct Definitions {
+    words: Vec<Word>,
+}
+
+impl WordList for Definitions {
+    fn new() -> Self {
+        Definitions {
+            words: vec![
+                (
+                    ":",
                     |s| s.get_curr_word() == ":" && s.mode_normal(),
                     start_compile,
                 ),
                 (
-                    |s: &Engine| {
-                        s.compiled_words
-                            .iter()
-                            .any(|c| c.starts_with(s.get_curr_word().as_str()))
-                            && s.mode_normal()
-                    },
+                    "<custom word>",
+                    |s: &Engine| is_compiled_word(s) && s.mode_normal(),
                     run_compiled,
                 ),
-                (|s| s.get_curr_word() == "bye" && s.mode_normal(), bye),
-                (|s| s.get_curr_word() == "nl" && s.mode_normal(), nl),
-                (|s| s.get_curr_word() == "emit" && s.mode_normal(), emit),
-                // Type ops
-                (|s| s.get_curr_word() == "to_int" && s.mode_normal(), to_int),
                 (
+                    "<compile>",
+                    |s| s.get_curr_word() != ";" && s.mode_compile(),
+                    compile,
+                ),
+                (
+                    ";",
+                    |s| s.get_curr_word() == ";" && s.mode_compile(),
+                    end_compile,
+                ),
+            ],
+        }
+    }
+
+    fn get_words(&self) -> Vec<Word> {
+        self.words.clone()
+    }
+}
+
+// Interaction with the outside world
+pub struct Io {
+    words: Vec<Word>,
+}
+
+impl WordList for Io {
+    fn new() -> Self {
+        Io {
+            words: vec![
+                (
+                    "bye",
+                    |s| s.get_curr_word() == "bye" && s.mode_normal(),
+                    bye,
+                ),
+                ("nl", |s| s.get_curr_word() == "nl" && s.mode_normal(), nl),
+                (
+                    "emit",
+                    |s| s.get_curr_word() == "emit" && s.mode_normal(),
+                    emit,
+                ),
+                // Silent mode
+                (
+                    "silent",
+                    |s| s.mode_normal() && s.get_curr_word() == "silent",
+                    silent,
+                ),
+                // Input
+                (
+                    "input",
+                    |s| s.mode_normal() && s.get_curr_word() == "input",
+                    input,
+                ),
+            ],
+        }
+    }
+
+    fn get_words(&self) -> Vec<Word> {
+        self.words.clone()
+    }
+}
+
+// Type conversions
+pub struct Conversions {
+    words: Vec<Word>,
+}
+
+impl WordList for Conversions {
+    fn new() -> Self {
+        Conversions {
+            words: vec![
+                (
+                    "to_int",
+                    |s| s.get_curr_word() == "to_int" && s.mode_normal(),
+                    to_int,
+                ),
+                (
+                    "to_long",
                     |s| s.get_curr_word() == "to_long" && s.mode_normal(),
                     to_long,
                 ),
                 (
+                    "to_float",
                     |s| s.get_curr_word() == "to_float" && s.mode_normal(),
                     to_float,
                 ),
                 (
+                    "to_double",
                     |s| s.get_curr_word() == "to_double" && s.mode_normal(),
                     to_double,
                 ),
                 (
+                    "to_byte",
                     |s| s.get_curr_word() == "to_byte" && s.mode_normal(),
                     to_byte,
                 ),
                 (
+                    "to_uint",
+                    |s| s.get_curr_word() == "to_uint" && s.mode_normal(),
+                    to_uint,
+                ),
+                (
+                    "to_ulong",
+                    |s| s.get_curr_word() == "to_ulong" && s.mode_normal(),
+                    to_ulong,
+                ),
+                (
+                    "to_int128",
+                    |s| s.get_curr_word() == "to_int128" && s.mode_normal(),
+                    to_int128,
+                ),
+                (
+                    "to_decimal",
+                    |s| s.get_curr_word() == "to_decimal" && s.mode_normal(),
+                    to_decimal,
+                ),
+                (
+                    "to_str",
                     |s| s.get_curr_word() == "to_str" && s.mode_normal(),
                     to_string,
                 ),
-                // String ops
+            ],
+        }
+    }
+
+    fn get_words(&self) -> Vec<Word> {
+        self.words.clone()
+    }
+}
+
+// String literals and operations
+pub struct Strings {
+    words: Vec<Word>,
+}
+
+impl WordList for Strings {
+    fn new() -> Self {
+        Strings {
+            words: vec![
                 (
+                    "concat",
                     |s| s.get_curr_word() == "concat" && s.mode_normal(),
                     string_concat,
                 ),
                 (
+                    "split",
                     |s| s.get_curr_word() == "split" && s.mode_normal(),
                     string_split,
                 ),
                 (
+                    "wsplit",
                     |s| s.get_curr_word() == "wsplit" && s.mode_normal(),
                     string_split_whitespace,
                 ),
+                // Characters
+                (
+                    "chars",
+                    |s| s.get_curr_word() == "chars" && s.mode_normal(),
+                    chars,
+                ),
+                (
+                    "char>int",
+                    |s| s.get_curr_word() == "char>int" && s.mode_normal(),
+                    char_to_int,
+                ),
+                (
+                    "int>char",
+                    |s| s.get_curr_word() == "int>char" && s.mode_normal(),
+                    int_to_char,
+                ),
+                (
+                    "alpha?",
+                    |s| s.get_curr_word() == "alpha?" && s.mode_normal(),
+                    is_alpha,
+                ),
+                (
+                    "digit?",
+                    |s| s.get_curr_word() == "digit?" && s.mode_normal(),
+                    is_digit,
+                ),
+                (
+                    "space?",
+                    |s| s.get_curr_word() == "space?" && s.mode_normal(),
+                    is_space,
+                ),
+                (
+                    "upper",
+                    |s| s.get_curr_word() == "upper" && s.mode_normal(),
+                    upper,
+                ),
+                (
+                    "lower",
+                    |s| s.get_curr_word() == "lower" && s.mode_normal(),
+                    lower,
+                ),
                 // Read string
                 (
+                    "\"",
                     |s| {
                         s.get_curr_word().starts_with("\"")
                             && s.get_curr_word().ends_with("\"")
@@ -204,52 +844,74 @@ impl WordList for Standard {
                     },
                     string_mode_toggle,
                 ),
-                (|s| s.mode_string(), string_creation),
-                // Silent mode
-                (|s| s.mode_normal() && s.get_curr_word() == "silent", silent),
-                // Input
-                (|s| s.mode_normal() && s.get_curr_word() == "input", input),
-                // Read number
+                ("<string>", |s| s.mode_string(), string_creation),
+            ],
+        }
+    }
+
+    fn get_words(&self) -> Vec<Word> {
+        self.words.clone()
+    }
+}
+
+// List literals { 1 2 3 } and operations, concat is in Strings
+pub struct Lists {
+    words: Vec<Word>,
+}
+
+impl WordList for Lists {
+    fn new() -> Self {
+        Lists {
+            words: vec![
                 (
-                    |s| s.get_curr_word().parse::<i32>().is_ok() && s.mode_normal(),
-                    int_number,
+                    "{",
+                    |s| s.get_curr_word() == "{" && s.mode_normal(),
+                    list_start,
                 ),
                 (
-                    |s: &Engine| {
-                        let mut word = s.get_curr_word();
-                        word.pop();
-                        word.parse::<i64>().is_ok()
-                            && s.get_curr_word().to_lowercase().ends_with("l")
-                            && s.mode_normal()
-                    },
-                    long_number,
+                    "}",
+                    |s| s.get_curr_word() == "}" && s.mode_normal(),
+                    list_end,
                 ),
                 (
-                    |s: &Engine| {
-                        let mut word: String = s.get_curr_word();
-                        word.pop();
-                        word.parse::<f32>().is_ok()
-                            && s.get_curr_word().to_lowercase().ends_with("f")
-                            && s.mode_normal()
-                    },
-                    float_number,
+                    "index",
+                    |s| s.get_curr_word() == "index" && s.mode_normal(),
+                    index,
                 ),
                 (
-                    |s: &Engine| s.get_curr_word().parse::<f64>().is_ok() && s.mode_normal(),
-                    double_number,
+                    "append",
+                    |s| s.get_curr_word() == "append" && s.mode_normal(),
+                    append,
                 ),
                 (
-                    |s: &Engine| {
-                        s.get_curr_word().starts_with("0x")
-                            && s.get_curr_word().len() == 4
-                            && s.mode_normal()
-                    },
-                    byte_number,
+                    "slice",
+                    |s| s.get_curr_word() == "slice" && s.mode_normal(),
+                    slice,
                 ),
-                (|s| s.get_curr_word() != ";" && s.mode_compile(), compile),
                 (
-                    |s| s.get_curr_word() == ";" && s.mode_compile(),
-                    end_compile,
+                    "reverse",
+                    |s| s.get_curr_word() == "reverse" && s.mode_normal(),
+                    reverse,
+                ),
+                (
+                    "sort",
+                    |s| s.get_curr_word() == "sort" && s.mode_normal(),
+                    sort,
+                ),
+                (
+                    "length",
+                    |s| s.get_curr_word() == "length" && s.mode_normal(),
+                    length,
+                ),
+                (
+                    "var>list",
+                    |s| s.get_curr_word() == "var>list" && s.mode_normal(),
+                    var_to_list,
+                ),
+                (
+                    "list>var",
+                    |s| s.get_curr_word() == "list>var" && s.mode_normal(),
+                    list_to_var,
                 ),
             ],
         }
@@ -259,3 +921,231 @@ impl WordList for Standard {
         self.words.clone()
     }
 }
+
+// Maps from strings and integers to values, length is in Lists
+pub struct Maps {
+    words: Vec<Word>,
+}
+
+impl WordList for Maps {
+    fn new() -> Self {
+        Maps {
+            words: vec![
+                (
+                    "map-new",
+                    |s| s.get_curr_word() == "map-new" && s.mode_normal(),
+                    map_new,
+                ),
+                (
+                    "map-put",
+                    |s| s.get_curr_word() == "map-put" && s.mode_normal(),
+                    map_put,
+                ),
+                (
+                    "map-get",
+                    |s| s.get_curr_word() == "map-get" && s.mode_normal(),
+                    map_get,
+                ),
+                (
+                    "map-has?",
+                    |s| s.get_curr_word() == "map-has?" && s.mode_normal(),
+                    map_has,
+                ),
+                (
+                    "map-remove",
+                    |s| s.get_curr_word() == "map-remove" && s.mode_normal(),
+                    map_remove,
+                ),
+                (
+                    "map-keys",
+                    |s| s.get_curr_word() == "map-keys" && s.mode_normal(),
+                    map_keys,
+                ),
+                (
+                    "map-values",
+                    |s| s.get_curr_word() == "map-values" && s.mode_normal(),
+                    map_values,
+                ),
+                (
+                    "map-each",
+                    |s| s.get_curr_word() == "map-each" && s.get_compiled_exec() && s.mode_normal(),
+                    map_each,
+                ),
+                (
+                    "map-next",
+                    |s| s.get_curr_word() == "map-next" && s.get_compiled_exec() && s.mode_normal(),
+                    map_next,
+                ),
+            ],
+        }
+    }
+
+    fn get_words(&self) -> Vec<Word> {
+        self.words.clone()
+    }
+}
+
+// Struct declarations and the words they generate
+pub struct Structs {
+    words: Vec<Word>,
+}
+
+impl WordList for Structs {
+    fn new() -> Self {
+        Structs {
+            words: vec![
+                (
+                    "struct",
+                    |s| s.get_curr_word() == "struct" && s.mode_normal(),
+                    struct_word,
+                ),
+                (
+                    "<struct>",
+                    |s| is_constructor(s, &s.get_curr_word()) && s.mode_normal(),
+                    construct,
+                ),
+                (
+                    "<struct>.<field>",
+                    |s| is_getter(s, &s.get_curr_word()) && s.mode_normal(),
+                    get_field,
+                ),
+                (
+                    "<struct>.<field>!",
+                    |s| is_setter(s, &s.get_curr_word()) && s.mode_normal(),
+                    set_field,
+                ),
+                (
+                    "<struct>?",
+                    |s| is_predicate(s, &s.get_curr_word()) && s.mode_normal(),
+                    instance_of,
+                ),
+            ],
+        }
+    }
+
+    fn get_words(&self) -> Vec<Word> {
+        self.words.clone()
+    }
+}
+
+// Execution tracing
+pub struct Diagnostics {
+    words: Vec<Word>,
+}
+
+impl WordList for Diagnostics {
+    fn new() -> Self {
+        Diagnostics {
+            words: vec![
+                (
+                    "trace",
+                    |s| s.get_curr_word() == "trace" && s.mode_normal(),
+                    trace_word,
+                ),
+                (
+                    "notrace",
+                    |s| s.get_curr_word() == "notrace" && s.mode_normal(),
+                    notrace_word,
+                ),
+                (
+                    "profile",
+                    |s| s.get_curr_word() == "profile" && s.mode_normal(),
+                    profile_word,
+                ),
+                (
+                    "noprofile",
+                    |s| s.get_curr_word() == "noprofile" && s.mode_normal(),
+                    noprofile_word,
+                ),
+                (
+                    "profile-report",
+                    |s| s.get_curr_word() == "profile-report" && s.mode_normal(),
+                    profile_report_word,
+                ),
+            ],
+        }
+    }
+
+    fn get_words(&self) -> Vec<Word> {
+        self.words.clone()
+    }
+}
+
+// Test harness: T{ inputs -> expected }T, assert and assert-eq
+pub struct Testing {
+    words: Vec<Word>,
+}
+
+impl WordList for Testing {
+    fn new() -> Self {
+        Testing {
+            words: vec![
+                (
+                    "T{",
+                    |s| s.get_curr_word() == "T{" && s.mode_normal(),
+                    test_start,
+                ),
+                (
+                    "->",
+                    |s| s.get_curr_word() == "->" && s.mode_normal(),
+                    test_arrow,
+                ),
+                (
+                    "}T",
+                    |s| s.get_curr_word() == "}T" && s.mode_normal(),
+                    test_end,
+                ),
+                (
+                    "assert",
+                    |s| s.get_curr_word() == "assert" && s.mode_normal(),
+                    assert_word,
+                ),
+                (
+                    "assert-eq",
+                    |s| s.get_curr_word() == "assert-eq" && s.mode_normal(),
+                    assert_eq_word,
+                ),
+            ],
+        }
+    }
+
+    fn get_words(&self) -> Vec<Word> {
+        self.words.clone()
+    }
+}
+
+// All of the above word lists in the order they have to be imported in
+pub struct Standard {
+    words: Vec<Word>,
+}
+
+impl WordList for Standard {
+    fn new() -> Self {
+        let mut words = Vec::new();
+
+        words.append(&mut Control::new().get_words());
+        words.append(&mut Variables::new().get_words());
+        words.append(&mut Math::new().get_words());
+        #[cfg(any(feature = "std", feature = "libm"))]
+        words.append(&mut ExtendedMath::new().get_words());
+        #[cfg(feature = "bigint")]
+        words.append(&mut BigIntegers::new().get_words());
+        words.append(&mut Logic::new().get_words());
+        words.append(&mut CoreStack::new().get_words());
+        words.append(&mut Definitions::new().get_words());
+        words.append(&mut Io::new().get_words());
+        words.append(&mut Conversions::new().get_words());
+        words.append(&mut Strings::new().get_words());
+        words.append(&mut Lists::new().get_words());
+        words.append(&mut Maps::new().get_words());
+        words.append(&mut Structs::new().get_words());
+        words.append(&mut Diagnostics::new().get_words());
+        words.append(&mut Testing::new().get_words());
+
+        Standard { words }
+    }
+
+    fn get_words(&self) -> Vec<Word> {
+        self.words.clone()
+    }
+}
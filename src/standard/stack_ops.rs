@@ -1,7 +1,5 @@
-use crate::{
-    errors::STACK_UNDERFLOW_ERROR,
-    prelude::{Engine, Types},
-};
+use crate::{errors::STACK_UNDERFLOW_ERROR, prelude::Engine};
+use alloc::string::{String, ToString};
 
 pub fn dup(s: &mut Engine) -> Result<String, String> {
     let head = s.main_stack.pop();
@@ -75,14 +73,7 @@ pub fn dot(s: &mut Engine) -> Result<String, String> {
         return Err(STACK_UNDERFLOW_ERROR.to_string());
     }
 
-    match head.unwrap() {
-        Types::Int(a) => Ok(a.to_string()),
-        Types::Long(a) => Ok(a.to_string()),
-        Types::Float(a) => Ok(a.to_string()),
-        Types::Double(a) => Ok(a.to_string()),
-        Types::Byte(a) => Ok(a.to_string()),
-        Types::Str(a) => Ok(a),
-    }
+    Ok(head.unwrap().to_string())
 }
 
 pub fn peek(s: &mut Engine) -> Result<String, String> {
@@ -92,12 +83,5 @@ pub fn peek(s: &mut Engine) -> Result<String, String> {
         return Err(STACK_UNDERFLOW_ERROR.to_string());
     }
 
-    match last.unwrap() {
-        Types::Int(a) => Ok(a.to_string()),
-        Types::Long(a) => Ok(a.to_string()),
-        Types::Float(a) => Ok(a.to_string()),
-        Types::Double(a) => Ok(a.to_string()),
-        Types::Byte(a) => Ok(a.to_string()),
-        Types::Str(a) => Ok(a.to_string()),
-    }
+    Ok(last.unwrap().to_string())
 }
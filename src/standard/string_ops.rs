@@ -2,6 +2,7 @@ use crate::{
     errors::{INVALID_TYPE_ERROR, STACK_UNDERFLOW_ERROR},
     prelude::{Engine, EngineMode, Types},
 };
+use alloc::string::{String, ToString};
 
 pub fn string_mode_toggle(s: &mut Engine) -> Result<String, String> {
     if s.mode_string() {
@@ -17,11 +18,11 @@ pub fn string_mode_toggle(s: &mut Engine) -> Result<String, String> {
 
 pub fn string_creation(s: &mut Engine) -> Result<String, String> {
     s.string_buffer.push_str(s.get_curr_word().as_str());
-    s.string_buffer.push_str(" ");
+    s.string_buffer.push(' ');
     Ok("".to_string())
 }
 
-// Join two strings without a space in between
+// Join two strings without a space in between, or two lists
 pub fn string_concat(s: &mut Engine) -> Result<String, String> {
     let a = s.main_stack.pop();
     let b = s.main_stack.pop();
@@ -34,6 +35,10 @@ pub fn string_concat(s: &mut Engine) -> Result<String, String> {
         (Types::Str(b), Types::Str(a)) => s
             .main_stack
             .push(Types::Str(a.trim_end().to_string() + b.trim_start())),
+        (Types::List(b), Types::List(mut a)) => {
+            a.extend(b);
+            s.main_stack.push(Types::List(a));
+        }
         _ => return Err(INVALID_TYPE_ERROR.to_string()),
     }
 
@@ -52,7 +57,6 @@ pub fn string_split(s: &mut Engine) -> Result<String, String> {
     match (delimiter.unwrap(), a.unwrap()) {
         (Types::Str(delimiter), Types::Str(a)) => {
             for splitted in a.split(delimiter.as_str()) {
-                println!("{}, {}", delimiter, splitted);
                 s.main_stack.push(Types::Str(splitted.to_string()));
             }
         }
@@ -0,0 +1,215 @@
+use super::word_ops::runs_as;
+use crate::{
+    errors::{INVALID_TYPE_ERROR, STACK_UNDERFLOW_ERROR, STRUCT_SYNTAX_ERROR},
+    prelude::{Engine, Struct, Types},
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+// Fields of the declared struct `name`
+fn fields<'a>(s: &'a Engine, name: &str) -> Option<&'a Vec<String>> {
+    s.structs
+        .iter()
+        .find(|(declared, _)| declared == name)
+        .map(|(_, fields)| fields)
+}
+
+// Struct and field of a getter like `point.x`
+fn field<'a>(s: &Engine, word: &'a str) -> Option<(&'a str, &'a str)> {
+    let (name, field) = word.split_once('.')?;
+    fields(s, name)?
+        .iter()
+        .any(|declared| declared == field)
+        .then_some((name, field))
+}
+
+// `point`
+pub fn is_constructor(s: &Engine, word: &str) -> bool {
+    fields(s, word).is_some()
+}
+
+// `point.x`
+pub fn is_getter(s: &Engine, word: &str) -> bool {
+    field(s, word).is_some()
+}
+
+// `point.x!`
+pub fn is_setter(s: &Engine, word: &str) -> bool {
+    word.strip_suffix('!')
+        .is_some_and(|getter| is_getter(s, getter))
+}
+
+// `point?`
+pub fn is_predicate(s: &Engine, word: &str) -> bool {
+    word.strip_suffix('?')
+        .is_some_and(|name| is_constructor(s, name))
+}
+
+// Number of values taken and left by the words of the declared structs
+pub fn struct_effect(s: &Engine, word: &str) -> Option<(usize, usize)> {
+    if let Some(fields) = fields(s, word) {
+        Some((fields.len(), 1))
+    } else if is_getter(s, word) || is_predicate(s, word) {
+        Some((1, 1))
+    } else if is_setter(s, word) {
+        Some((2, 1))
+    } else {
+        None
+    }
+}
+
+// A value is a `name` if it was made by the constructor of its current
+// declaration, values made before the struct was declared again aren't
+fn is_instance(s: &Engine, val: &Types, name: &str) -> bool {
+    match (val, fields(s, name)) {
+        (Types::Struct(val), Some(fields)) => {
+            val.name == name
+                && val.fields.len() == fields.len()
+                && val
+                    .fields
+                    .iter()
+                    .zip(fields.iter())
+                    .all(|((a, _), b)| a == b)
+        }
+        _ => false,
+    }
+}
+
+// struct <name> <field>... end-struct
+//
+// Declaring a struct again replaces its fields. Names can't contain a `.`,
+// the fields of a struct have to be different and can't end with `!` or `?`,
+// and the generated words can't be shadowed by other words.
+pub fn struct_word(s: &mut Engine) -> Result<String, String> {
+    let mut tokens = Vec::new();
+    loop {
+        *s.curr_word_idx.last_mut().unwrap() += 1;
+        match s.get_curr_word().as_str() {
+            "" => return Err(STRUCT_SYNTAX_ERROR.to_string()),
+            "end-struct" => break,
+            token => tokens.push(token.to_string()),
+        }
+    }
+
+    if tokens.is_empty() || tokens.iter().any(|token| token.contains('.')) {
+        return Err(STRUCT_SYNTAX_ERROR.to_string());
+    }
+
+    let name = tokens.remove(0);
+    for (i, field) in tokens.iter().enumerate() {
+        if tokens[..i].contains(field) || field.ends_with('!') || field.ends_with('?') {
+            return Err(STRUCT_SYNTAX_ERROR.to_string());
+        }
+    }
+
+    let mut words = vec![
+        (name.clone(), "<struct>"),
+        (name.clone() + "?", "<struct>?"),
+    ];
+    for field in &tokens {
+        let getter = format!("{}.{}", name, field);
+        words.push((getter.clone() + "!", "<struct>.<field>!"));
+        words.push((getter, "<struct>.<field>"));
+    }
+    for (word, kind) in words {
+        if runs_as(s, &word).is_some_and(|name| name != kind) {
+            return Err(STRUCT_SYNTAX_ERROR.to_string());
+        }
+    }
+
+    match s.structs.iter_mut().find(|(declared, _)| *declared == name) {
+        Some((_, fields)) => *fields = tokens,
+        None => s.structs.push((name, tokens)),
+    }
+
+    Ok("".to_string())
+}
+
+// ( field... -- struct ) the first field is the deepest value
+pub fn construct(s: &mut Engine) -> Result<String, String> {
+    let name = s.get_curr_word();
+    let fields = fields(s, &name).unwrap().clone();
+
+    if s.main_stack.len() < fields.len() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    let values = s.main_stack.split_off(s.main_stack.len() - fields.len());
+    s.main_stack.push(Types::Struct(Struct {
+        name,
+        fields: fields.into_iter().zip(values).collect(),
+    }));
+
+    Ok("".to_string())
+}
+
+// ( struct -- val )
+pub fn get_field(s: &mut Engine) -> Result<String, String> {
+    let word = s.get_curr_word();
+    let (name, field) = field(s, &word).unwrap();
+
+    let val = s.main_stack.pop();
+
+    if val.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    let val = val.unwrap();
+    if !is_instance(s, &val, name) {
+        return Err(INVALID_TYPE_ERROR.to_string());
+    }
+
+    if let Types::Struct(val) = val {
+        let (_, val) = val.fields.into_iter().find(|(f, _)| f == field).unwrap();
+        s.main_stack.push(val);
+    }
+
+    Ok("".to_string())
+}
+
+// ( struct val -- struct )
+pub fn set_field(s: &mut Engine) -> Result<String, String> {
+    let word = s.get_curr_word();
+    let (name, field) = field(s, word.strip_suffix('!').unwrap()).unwrap();
+
+    let new_val = s.main_stack.pop();
+    let val = s.main_stack.pop();
+
+    if new_val.is_none() || val.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    let val = val.unwrap();
+    if !is_instance(s, &val, name) {
+        return Err(INVALID_TYPE_ERROR.to_string());
+    }
+
+    if let Types::Struct(mut val) = val {
+        let (_, old) = val.fields.iter_mut().find(|(f, _)| f == field).unwrap();
+        *old = new_val.unwrap();
+        s.main_stack.push(Types::Struct(val));
+    }
+
+    Ok("".to_string())
+}
+
+// ( val -- bool )
+pub fn instance_of(s: &mut Engine) -> Result<String, String> {
+    let word = s.get_curr_word();
+    let name = word.strip_suffix('?').unwrap();
+
+    let val = s.main_stack.pop();
+
+    if val.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    let result = is_instance(s, &val.unwrap(), name);
+    s.main_stack.push(Types::Bool(result));
+
+    Ok("".to_string())
+}
@@ -0,0 +1,98 @@
+use crate::{
+    errors::{NOT_A_FLAG_ERROR, STACK_UNDERFLOW_ERROR, TEST_FAILED_ERROR, TEST_SYNTAX_ERROR},
+    prelude::{Engine, Types},
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+fn format_values(values: &[Types]) -> String {
+    let mut out = "[".to_string();
+    for val in values.iter() {
+        out += &format!(" {}", val);
+    }
+    out + " ]"
+}
+
+// T{ records the depth of the stack ...
+pub fn test_start(s: &mut Engine) -> Result<String, String> {
+    s.test_stack.push((s.main_stack.len(), None));
+    Ok("".to_string())
+}
+
+// ... -> takes the values produced since T{ as the actual results ...
+pub fn test_arrow(s: &mut Engine) -> Result<String, String> {
+    let depth = match s.test_stack.last() {
+        Some((depth, None)) => *depth,
+        _ => return Err(TEST_SYNTAX_ERROR.to_string()),
+    };
+    if s.main_stack.len() < depth {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    let actual = s.main_stack.split_off(depth);
+    s.test_stack.last_mut().unwrap().1 = Some(actual);
+    Ok("".to_string())
+}
+
+// ... and }T compares them with the values produced since ->
+pub fn test_end(s: &mut Engine) -> Result<String, String> {
+    let (depth, actual) = match s.test_stack.pop() {
+        Some((depth, Some(actual))) => (depth, actual),
+        _ => return Err(TEST_SYNTAX_ERROR.to_string()),
+    };
+    if s.main_stack.len() < depth {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    let expected = s.main_stack.split_off(depth);
+    if expected != actual {
+        s.tests_failed += 1;
+        return Err(format!(
+            "{}expected {} got {}",
+            TEST_FAILED_ERROR,
+            format_values(&expected),
+            format_values(&actual)
+        ));
+    }
+
+    s.tests_passed += 1;
+    Ok("".to_string())
+}
+
+pub fn assert_word(s: &mut Engine) -> Result<String, String> {
+    match s.main_stack.pop().map(|val| val.truthy()) {
+        Some(Some(false)) => {
+            s.tests_failed += 1;
+            Err(TEST_FAILED_ERROR.to_string() + "assertion")
+        }
+        Some(Some(true)) => {
+            s.tests_passed += 1;
+            Ok("".to_string())
+        }
+        Some(None) => Err(NOT_A_FLAG_ERROR.to_string()),
+        None => Err(STACK_UNDERFLOW_ERROR.to_string()),
+    }
+}
+
+// ( actual expected -- ) values have to be of the same type to be equal
+pub fn assert_eq_word(s: &mut Engine) -> Result<String, String> {
+    if s.main_stack.len() < 2 {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    let expected = s.main_stack.pop().unwrap();
+    let actual = s.main_stack.pop().unwrap();
+
+    if actual != expected {
+        s.tests_failed += 1;
+        return Err(format!(
+            "{}expected {} got {}",
+            TEST_FAILED_ERROR, expected, actual
+        ));
+    }
+
+    s.tests_passed += 1;
+    Ok("".to_string())
+}
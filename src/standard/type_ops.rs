@@ -1,7 +1,35 @@
 use crate::{
-    errors::{STACK_UNDERFLOW_ERROR, TYPE_CONVERSION_FAILURE_ERROR},
-    prelude::{Byte, Double, Engine, Float, Int, Long, Types},
+    errors::{INVALID_TYPE_ERROR, STACK_UNDERFLOW_ERROR, TYPE_CONVERSION_FAILURE_ERROR},
+    prelude::{Byte, Decimal, Double, Engine, Float, Int, Int128, Long, Types, UInt, ULong},
 };
+use alloc::string::{String, ToString};
+#[cfg(feature = "bigint")]
+use {
+    crate::prelude::BigInt,
+    num_traits::{FromPrimitive, ToPrimitive},
+};
+
+// A value only converts to a fixed width type that holds it
+fn fits<T>(val: Option<T>) -> Result<T, String> {
+    val.ok_or(TYPE_CONVERSION_FAILURE_ERROR.to_string())
+}
+
+// A Bool converts to the Int flag it replaces
+fn flag(val: bool) -> Int {
+    if val {
+        -1
+    } else {
+        0
+    }
+}
+
+// Floats convert to integers toward zero, NaN and values out of range don't
+fn integer<T: TryFrom<Int128>>(val: Double) -> Option<T> {
+    if val.is_nan() || val < Int128::MIN as Double || val >= -(Int128::MIN as Double) {
+        return None;
+    }
+    T::try_from(val as Int128).ok()
+}
 
 pub fn to_string(s: &mut Engine) -> Result<String, String> {
     let val = s.main_stack.pop();
@@ -16,6 +44,18 @@ pub fn to_string(s: &mut Engine) -> Result<String, String> {
         crate::prelude::Types::Float(val) => s.main_stack.push(Types::Str(val.to_string())),
         crate::prelude::Types::Double(val) => s.main_stack.push(Types::Str(val.to_string())),
         crate::prelude::Types::Byte(val) => s.main_stack.push(Types::Str(val.to_string())),
+        crate::prelude::Types::UInt(val) => s.main_stack.push(Types::Str(val.to_string())),
+        crate::prelude::Types::ULong(val) => s.main_stack.push(Types::Str(val.to_string())),
+        crate::prelude::Types::Int128(val) => s.main_stack.push(Types::Str(val.to_string())),
+        crate::prelude::Types::Decimal(val) => s.main_stack.push(Types::Str(val.to_string())),
+        #[cfg(feature = "bigint")]
+        crate::prelude::Types::BigInt(val) => s.main_stack.push(Types::Str(val.to_string())),
+        crate::prelude::Types::Bool(val) => s.main_stack.push(Types::Str(val.to_string())),
+        crate::prelude::Types::Char(val) => s.main_stack.push(Types::Str(val.to_string())),
+        val @ (crate::prelude::Types::List(_)
+        | crate::prelude::Types::Map(_)
+        | crate::prelude::Types::Struct(_)
+        | crate::prelude::Types::VarRef(_)) => s.main_stack.push(Types::Str(val.to_string())),
         crate::prelude::Types::Str(val) => s.main_stack.push(Types::Str(val)),
     }
 
@@ -31,14 +71,38 @@ pub fn to_int(s: &mut Engine) -> Result<String, String> {
 
     match val.unwrap() {
         crate::prelude::Types::Int(val) => s.main_stack.push(Types::Int(val)),
-        crate::prelude::Types::Long(val) => s.main_stack.push(Types::Int(val as Int)),
-        crate::prelude::Types::Float(val) => s.main_stack.push(Types::Int(val as Int)),
-        crate::prelude::Types::Double(val) => s.main_stack.push(Types::Int(val as Int)),
+        crate::prelude::Types::Long(val) => s
+            .main_stack
+            .push(Types::Int(fits(Int::try_from(val).ok())?)),
+        crate::prelude::Types::Float(val) => {
+            s.main_stack.push(Types::Int(fits(integer(val as Double))?))
+        }
+        crate::prelude::Types::Double(val) => s.main_stack.push(Types::Int(fits(integer(val))?)),
         crate::prelude::Types::Byte(val) => s.main_stack.push(Types::Int(val as Int)),
+        crate::prelude::Types::UInt(val) => s
+            .main_stack
+            .push(Types::Int(fits(Int::try_from(val).ok())?)),
+        crate::prelude::Types::ULong(val) => s
+            .main_stack
+            .push(Types::Int(fits(Int::try_from(val).ok())?)),
+        crate::prelude::Types::Int128(val) => s
+            .main_stack
+            .push(Types::Int(fits(Int::try_from(val).ok())?)),
+        crate::prelude::Types::Decimal(val) => s
+            .main_stack
+            .push(Types::Int(fits(Int::try_from(val.to_int128()).ok())?)),
+        #[cfg(feature = "bigint")]
+        crate::prelude::Types::BigInt(val) => s.main_stack.push(Types::Int(fits(val.to_i32())?)),
+        crate::prelude::Types::Bool(val) => s.main_stack.push(Types::Int(flag(val))),
+        crate::prelude::Types::Char(_)
+        | crate::prelude::Types::List(_)
+        | crate::prelude::Types::Map(_)
+        | crate::prelude::Types::Struct(_)
+        | crate::prelude::Types::VarRef(_) => return Err(INVALID_TYPE_ERROR.to_string()),
         crate::prelude::Types::Str(val) => {
             let parsed = val.parse::<Int>();
-            if parsed.is_ok() {
-                s.main_stack.push(Types::Int(parsed.unwrap()));
+            if let Ok(parsed) = parsed {
+                s.main_stack.push(Types::Int(parsed));
             } else {
                 return Err(TYPE_CONVERSION_FAILURE_ERROR.to_string());
             }
@@ -58,13 +122,33 @@ pub fn to_long(s: &mut Engine) -> Result<String, String> {
     match val.unwrap() {
         crate::prelude::Types::Int(val) => s.main_stack.push(Types::Long(val as Long)),
         crate::prelude::Types::Long(val) => s.main_stack.push(Types::Long(val)),
-        crate::prelude::Types::Float(val) => s.main_stack.push(Types::Long(val as Long)),
-        crate::prelude::Types::Double(val) => s.main_stack.push(Types::Long(val as Long)),
+        crate::prelude::Types::Float(val) => s
+            .main_stack
+            .push(Types::Long(fits(integer(val as Double))?)),
+        crate::prelude::Types::Double(val) => s.main_stack.push(Types::Long(fits(integer(val))?)),
         crate::prelude::Types::Byte(val) => s.main_stack.push(Types::Long(val as Long)),
+        crate::prelude::Types::UInt(val) => s.main_stack.push(Types::Long(val as Long)),
+        crate::prelude::Types::ULong(val) => s
+            .main_stack
+            .push(Types::Long(fits(Long::try_from(val).ok())?)),
+        crate::prelude::Types::Int128(val) => s
+            .main_stack
+            .push(Types::Long(fits(Long::try_from(val).ok())?)),
+        crate::prelude::Types::Decimal(val) => s
+            .main_stack
+            .push(Types::Long(fits(Long::try_from(val.to_int128()).ok())?)),
+        #[cfg(feature = "bigint")]
+        crate::prelude::Types::BigInt(val) => s.main_stack.push(Types::Long(fits(val.to_i64())?)),
+        crate::prelude::Types::Bool(val) => s.main_stack.push(Types::Long(flag(val) as Long)),
+        crate::prelude::Types::Char(_)
+        | crate::prelude::Types::List(_)
+        | crate::prelude::Types::Map(_)
+        | crate::prelude::Types::Struct(_)
+        | crate::prelude::Types::VarRef(_) => return Err(INVALID_TYPE_ERROR.to_string()),
         crate::prelude::Types::Str(val) => {
             let parsed = val.parse::<Long>();
-            if parsed.is_ok() {
-                s.main_stack.push(Types::Long(parsed.unwrap()));
+            if let Ok(parsed) = parsed {
+                s.main_stack.push(Types::Long(parsed));
             } else {
                 return Err(TYPE_CONVERSION_FAILURE_ERROR.to_string());
             }
@@ -87,10 +171,22 @@ pub fn to_float(s: &mut Engine) -> Result<String, String> {
         crate::prelude::Types::Float(val) => s.main_stack.push(Types::Float(val as Float)),
         crate::prelude::Types::Double(val) => s.main_stack.push(Types::Float(val as Float)),
         crate::prelude::Types::Byte(val) => s.main_stack.push(Types::Float(val as Float)),
+        crate::prelude::Types::UInt(val) => s.main_stack.push(Types::Float(val as Float)),
+        crate::prelude::Types::ULong(val) => s.main_stack.push(Types::Float(val as Float)),
+        crate::prelude::Types::Int128(val) => s.main_stack.push(Types::Float(val as Float)),
+        crate::prelude::Types::Decimal(val) => s.main_stack.push(Types::Float(val.to_float())),
+        #[cfg(feature = "bigint")]
+        crate::prelude::Types::BigInt(val) => s.main_stack.push(Types::Float(fits(val.to_f32())?)),
+        crate::prelude::Types::Bool(val) => s.main_stack.push(Types::Float(flag(val) as Float)),
+        crate::prelude::Types::Char(_)
+        | crate::prelude::Types::List(_)
+        | crate::prelude::Types::Map(_)
+        | crate::prelude::Types::Struct(_)
+        | crate::prelude::Types::VarRef(_) => return Err(INVALID_TYPE_ERROR.to_string()),
         crate::prelude::Types::Str(val) => {
             let parsed = val.parse::<Float>();
-            if parsed.is_ok() {
-                s.main_stack.push(Types::Float(parsed.unwrap()));
+            if let Ok(parsed) = parsed {
+                s.main_stack.push(Types::Float(parsed));
             } else {
                 return Err(TYPE_CONVERSION_FAILURE_ERROR.to_string());
             }
@@ -113,10 +209,22 @@ pub fn to_double(s: &mut Engine) -> Result<String, String> {
         crate::prelude::Types::Float(val) => s.main_stack.push(Types::Double(val as Double)),
         crate::prelude::Types::Double(val) => s.main_stack.push(Types::Double(val as Double)),
         crate::prelude::Types::Byte(val) => s.main_stack.push(Types::Double(val as Double)),
+        crate::prelude::Types::UInt(val) => s.main_stack.push(Types::Double(val as Double)),
+        crate::prelude::Types::ULong(val) => s.main_stack.push(Types::Double(val as Double)),
+        crate::prelude::Types::Int128(val) => s.main_stack.push(Types::Double(val as Double)),
+        crate::prelude::Types::Decimal(val) => s.main_stack.push(Types::Double(val.to_double())),
+        #[cfg(feature = "bigint")]
+        crate::prelude::Types::BigInt(val) => s.main_stack.push(Types::Double(fits(val.to_f64())?)),
+        crate::prelude::Types::Bool(val) => s.main_stack.push(Types::Double(flag(val) as Double)),
+        crate::prelude::Types::Char(_)
+        | crate::prelude::Types::List(_)
+        | crate::prelude::Types::Map(_)
+        | crate::prelude::Types::Struct(_)
+        | crate::prelude::Types::VarRef(_) => return Err(INVALID_TYPE_ERROR.to_string()),
         crate::prelude::Types::Str(val) => {
             let parsed = val.parse::<Double>();
-            if parsed.is_ok() {
-                s.main_stack.push(Types::Double(parsed.unwrap()));
+            if let Ok(parsed) = parsed {
+                s.main_stack.push(Types::Double(parsed));
             } else {
                 return Err(TYPE_CONVERSION_FAILURE_ERROR.to_string());
             }
@@ -134,15 +242,294 @@ pub fn to_byte(s: &mut Engine) -> Result<String, String> {
     }
 
     match val.unwrap() {
-        crate::prelude::Types::Int(val) => s.main_stack.push(Types::Byte(val as Byte)),
-        crate::prelude::Types::Long(val) => s.main_stack.push(Types::Byte(val as Byte)),
-        crate::prelude::Types::Float(val) => s.main_stack.push(Types::Byte(val as Byte)),
-        crate::prelude::Types::Double(val) => s.main_stack.push(Types::Byte(val as Byte)),
+        crate::prelude::Types::Int(val) => s
+            .main_stack
+            .push(Types::Byte(fits(Byte::try_from(val).ok())?)),
+        crate::prelude::Types::Long(val) => s
+            .main_stack
+            .push(Types::Byte(fits(Byte::try_from(val).ok())?)),
+        crate::prelude::Types::Float(val) => s
+            .main_stack
+            .push(Types::Byte(fits(integer(val as Double))?)),
+        crate::prelude::Types::Double(val) => s.main_stack.push(Types::Byte(fits(integer(val))?)),
         crate::prelude::Types::Byte(val) => s.main_stack.push(Types::Byte(val as Byte)),
+        crate::prelude::Types::UInt(val) => s
+            .main_stack
+            .push(Types::Byte(fits(Byte::try_from(val).ok())?)),
+        crate::prelude::Types::ULong(val) => s
+            .main_stack
+            .push(Types::Byte(fits(Byte::try_from(val).ok())?)),
+        crate::prelude::Types::Int128(val) => s
+            .main_stack
+            .push(Types::Byte(fits(Byte::try_from(val).ok())?)),
+        crate::prelude::Types::Decimal(val) => s
+            .main_stack
+            .push(Types::Byte(fits(Byte::try_from(val.to_int128()).ok())?)),
+        #[cfg(feature = "bigint")]
+        crate::prelude::Types::BigInt(val) => s.main_stack.push(Types::Byte(fits(val.to_u8())?)),
+        crate::prelude::Types::Bool(val) => s
+            .main_stack
+            .push(Types::Byte(fits(Byte::try_from(flag(val)).ok())?)),
+        crate::prelude::Types::Char(_)
+        | crate::prelude::Types::List(_)
+        | crate::prelude::Types::Map(_)
+        | crate::prelude::Types::Struct(_)
+        | crate::prelude::Types::VarRef(_) => return Err(INVALID_TYPE_ERROR.to_string()),
+        crate::prelude::Types::Str(val) => {
+            // Decimal like the output of to_str, or hex like a byte literal
+            let parsed = match val.strip_prefix("0x") {
+                Some(hex) => Byte::from_str_radix(hex, 16),
+                None => val.parse::<Byte>(),
+            };
+            if let Ok(parsed) = parsed {
+                s.main_stack.push(Types::Byte(parsed));
+            } else {
+                return Err(TYPE_CONVERSION_FAILURE_ERROR.to_string());
+            }
+        }
+    }
+
+    Ok("".to_string())
+}
+
+pub fn to_uint(s: &mut Engine) -> Result<String, String> {
+    let val = s.main_stack.pop();
+
+    if val.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    match val.unwrap() {
+        crate::prelude::Types::Int(val) => s
+            .main_stack
+            .push(Types::UInt(fits(UInt::try_from(val).ok())?)),
+        crate::prelude::Types::Long(val) => s
+            .main_stack
+            .push(Types::UInt(fits(UInt::try_from(val).ok())?)),
+        crate::prelude::Types::Float(val) => s
+            .main_stack
+            .push(Types::UInt(fits(integer(val as Double))?)),
+        crate::prelude::Types::Double(val) => s.main_stack.push(Types::UInt(fits(integer(val))?)),
+        crate::prelude::Types::Byte(val) => s.main_stack.push(Types::UInt(val as UInt)),
+        crate::prelude::Types::UInt(val) => s.main_stack.push(Types::UInt(val)),
+        crate::prelude::Types::ULong(val) => s
+            .main_stack
+            .push(Types::UInt(fits(UInt::try_from(val).ok())?)),
+        crate::prelude::Types::Int128(val) => s
+            .main_stack
+            .push(Types::UInt(fits(UInt::try_from(val).ok())?)),
+        crate::prelude::Types::Decimal(val) => s
+            .main_stack
+            .push(Types::UInt(fits(UInt::try_from(val.to_int128()).ok())?)),
+        #[cfg(feature = "bigint")]
+        crate::prelude::Types::BigInt(val) => s.main_stack.push(Types::UInt(fits(val.to_u32())?)),
+        crate::prelude::Types::Bool(val) => s
+            .main_stack
+            .push(Types::UInt(fits(UInt::try_from(flag(val)).ok())?)),
+        crate::prelude::Types::Char(_)
+        | crate::prelude::Types::List(_)
+        | crate::prelude::Types::Map(_)
+        | crate::prelude::Types::Struct(_)
+        | crate::prelude::Types::VarRef(_) => return Err(INVALID_TYPE_ERROR.to_string()),
+        crate::prelude::Types::Str(val) => {
+            let parsed = val.parse::<UInt>();
+            if let Ok(parsed) = parsed {
+                s.main_stack.push(Types::UInt(parsed));
+            } else {
+                return Err(TYPE_CONVERSION_FAILURE_ERROR.to_string());
+            }
+        }
+    }
+
+    Ok("".to_string())
+}
+
+pub fn to_ulong(s: &mut Engine) -> Result<String, String> {
+    let val = s.main_stack.pop();
+
+    if val.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    match val.unwrap() {
+        crate::prelude::Types::Int(val) => s
+            .main_stack
+            .push(Types::ULong(fits(ULong::try_from(val).ok())?)),
+        crate::prelude::Types::Long(val) => s
+            .main_stack
+            .push(Types::ULong(fits(ULong::try_from(val).ok())?)),
+        crate::prelude::Types::Float(val) => s
+            .main_stack
+            .push(Types::ULong(fits(integer(val as Double))?)),
+        crate::prelude::Types::Double(val) => s.main_stack.push(Types::ULong(fits(integer(val))?)),
+        crate::prelude::Types::Byte(val) => s.main_stack.push(Types::ULong(val as ULong)),
+        crate::prelude::Types::UInt(val) => s.main_stack.push(Types::ULong(val as ULong)),
+        crate::prelude::Types::ULong(val) => s.main_stack.push(Types::ULong(val)),
+        crate::prelude::Types::Int128(val) => s
+            .main_stack
+            .push(Types::ULong(fits(ULong::try_from(val).ok())?)),
+        crate::prelude::Types::Decimal(val) => s
+            .main_stack
+            .push(Types::ULong(fits(ULong::try_from(val.to_int128()).ok())?)),
+        #[cfg(feature = "bigint")]
+        crate::prelude::Types::BigInt(val) => s.main_stack.push(Types::ULong(fits(val.to_u64())?)),
+        crate::prelude::Types::Bool(val) => s
+            .main_stack
+            .push(Types::ULong(fits(ULong::try_from(flag(val)).ok())?)),
+        crate::prelude::Types::Char(_)
+        | crate::prelude::Types::List(_)
+        | crate::prelude::Types::Map(_)
+        | crate::prelude::Types::Struct(_)
+        | crate::prelude::Types::VarRef(_) => return Err(INVALID_TYPE_ERROR.to_string()),
+        crate::prelude::Types::Str(val) => {
+            let parsed = val.parse::<ULong>();
+            if let Ok(parsed) = parsed {
+                s.main_stack.push(Types::ULong(parsed));
+            } else {
+                return Err(TYPE_CONVERSION_FAILURE_ERROR.to_string());
+            }
+        }
+    }
+
+    Ok("".to_string())
+}
+
+pub fn to_int128(s: &mut Engine) -> Result<String, String> {
+    let val = s.main_stack.pop();
+
+    if val.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    match val.unwrap() {
+        crate::prelude::Types::Int(val) => s.main_stack.push(Types::Int128(val as Int128)),
+        crate::prelude::Types::Long(val) => s.main_stack.push(Types::Int128(val as Int128)),
+        crate::prelude::Types::Float(val) => s
+            .main_stack
+            .push(Types::Int128(fits(integer(val as Double))?)),
+        crate::prelude::Types::Double(val) => s.main_stack.push(Types::Int128(fits(integer(val))?)),
+        crate::prelude::Types::Byte(val) => s.main_stack.push(Types::Int128(val as Int128)),
+        crate::prelude::Types::UInt(val) => s.main_stack.push(Types::Int128(val as Int128)),
+        crate::prelude::Types::ULong(val) => s.main_stack.push(Types::Int128(val as Int128)),
+        crate::prelude::Types::Int128(val) => s.main_stack.push(Types::Int128(val)),
+        crate::prelude::Types::Decimal(val) => s.main_stack.push(Types::Int128(val.to_int128())),
+        #[cfg(feature = "bigint")]
+        crate::prelude::Types::BigInt(val) => {
+            s.main_stack.push(Types::Int128(fits(val.to_i128())?))
+        }
+        crate::prelude::Types::Bool(val) => s.main_stack.push(Types::Int128(flag(val) as Int128)),
+        crate::prelude::Types::Char(_)
+        | crate::prelude::Types::List(_)
+        | crate::prelude::Types::Map(_)
+        | crate::prelude::Types::Struct(_)
+        | crate::prelude::Types::VarRef(_) => return Err(INVALID_TYPE_ERROR.to_string()),
+        crate::prelude::Types::Str(val) => {
+            let parsed = val.parse::<Int128>();
+            if let Ok(parsed) = parsed {
+                s.main_stack.push(Types::Int128(parsed));
+            } else {
+                return Err(TYPE_CONVERSION_FAILURE_ERROR.to_string());
+            }
+        }
+    }
+
+    Ok("".to_string())
+}
+
+#[cfg(feature = "bigint")]
+pub fn to_bigint(s: &mut Engine) -> Result<String, String> {
+    let val = s.main_stack.pop();
+
+    if val.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    match val.unwrap() {
+        crate::prelude::Types::Int(val) => s.main_stack.push(Types::BigInt(BigInt::from(val))),
+        crate::prelude::Types::Long(val) => s.main_stack.push(Types::BigInt(BigInt::from(val))),
+        crate::prelude::Types::Float(val) => s
+            .main_stack
+            .push(Types::BigInt(fits(BigInt::from_f32(val))?)),
+        crate::prelude::Types::Double(val) => s
+            .main_stack
+            .push(Types::BigInt(fits(BigInt::from_f64(val))?)),
+        crate::prelude::Types::Byte(val) => s.main_stack.push(Types::BigInt(BigInt::from(val))),
+        crate::prelude::Types::UInt(val) => s.main_stack.push(Types::BigInt(BigInt::from(val))),
+        crate::prelude::Types::ULong(val) => s.main_stack.push(Types::BigInt(BigInt::from(val))),
+        crate::prelude::Types::Int128(val) => s.main_stack.push(Types::BigInt(BigInt::from(val))),
+        crate::prelude::Types::Decimal(val) => s
+            .main_stack
+            .push(Types::BigInt(BigInt::from(val.to_int128()))),
+        crate::prelude::Types::BigInt(val) => s.main_stack.push(Types::BigInt(val)),
+        crate::prelude::Types::Bool(val) => {
+            s.main_stack.push(Types::BigInt(BigInt::from(flag(val))))
+        }
+        crate::prelude::Types::Char(_)
+        | crate::prelude::Types::List(_)
+        | crate::prelude::Types::Map(_)
+        | crate::prelude::Types::Struct(_)
+        | crate::prelude::Types::VarRef(_) => return Err(INVALID_TYPE_ERROR.to_string()),
+        crate::prelude::Types::Str(val) => {
+            let parsed = val.parse::<BigInt>();
+            if let Ok(parsed) = parsed {
+                s.main_stack.push(Types::BigInt(parsed));
+            } else {
+                return Err(TYPE_CONVERSION_FAILURE_ERROR.to_string());
+            }
+        }
+    }
+
+    Ok("".to_string())
+}
+
+pub fn to_decimal(s: &mut Engine) -> Result<String, String> {
+    let val = s.main_stack.pop();
+
+    if val.is_none() {
+        return Err(STACK_UNDERFLOW_ERROR.to_string());
+    }
+
+    let conversion_failure = || TYPE_CONVERSION_FAILURE_ERROR.to_string();
+    match val.unwrap() {
+        crate::prelude::Types::Int(val) => s
+            .main_stack
+            .push(Types::Decimal(Decimal::from(val as Int128))),
+        crate::prelude::Types::Long(val) => s
+            .main_stack
+            .push(Types::Decimal(Decimal::from(val as Int128))),
+        crate::prelude::Types::Float(val) => s.main_stack.push(Types::Decimal(
+            Decimal::from_float(val).ok_or_else(conversion_failure)?,
+        )),
+        crate::prelude::Types::Double(val) => s.main_stack.push(Types::Decimal(
+            Decimal::from_double(val).ok_or_else(conversion_failure)?,
+        )),
+        crate::prelude::Types::Byte(val) => s
+            .main_stack
+            .push(Types::Decimal(Decimal::from(val as Int128))),
+        crate::prelude::Types::UInt(val) => s
+            .main_stack
+            .push(Types::Decimal(Decimal::from(val as Int128))),
+        crate::prelude::Types::ULong(val) => s
+            .main_stack
+            .push(Types::Decimal(Decimal::from(val as Int128))),
+        crate::prelude::Types::Int128(val) => s.main_stack.push(Types::Decimal(Decimal::from(val))),
+        #[cfg(feature = "bigint")]
+        crate::prelude::Types::BigInt(val) => s
+            .main_stack
+            .push(Types::Decimal(Decimal::from(fits(val.to_i128())?))),
+        crate::prelude::Types::Decimal(val) => s.main_stack.push(Types::Decimal(val)),
+        crate::prelude::Types::Bool(val) => s
+            .main_stack
+            .push(Types::Decimal(Decimal::from(flag(val) as Int128))),
+        crate::prelude::Types::Char(_)
+        | crate::prelude::Types::List(_)
+        | crate::prelude::Types::Map(_)
+        | crate::prelude::Types::Struct(_)
+        | crate::prelude::Types::VarRef(_) => return Err(INVALID_TYPE_ERROR.to_string()),
         crate::prelude::Types::Str(val) => {
-            let parsed = Byte::from_str_radix(val.as_str(), 16);
-            if parsed.is_ok() {
-                s.main_stack.push(Types::Byte(parsed.unwrap()));
+            let parsed = val.parse::<Decimal>();
+            if let Ok(parsed) = parsed {
+                s.main_stack.push(Types::Decimal(parsed));
             } else {
                 return Err(TYPE_CONVERSION_FAILURE_ERROR.to_string());
             }
@@ -1,4 +1,126 @@
-use crate::prelude::{Byte, Double, Engine, Float, Int, Long, Types};
+#[cfg(feature = "bigint")]
+use crate::prelude::BigInt;
+use crate::prelude::{Byte, Decimal, Double, Engine, Float, Int, Int128, Long, Types, UInt, ULong};
+use alloc::string::{String, ToString};
+
+pub fn is_int(word: &str) -> bool {
+    word.parse::<Int>().is_ok()
+}
+
+pub fn is_long(word: &str) -> bool {
+    match word.strip_suffix(['l', 'L']) {
+        Some(number) => number.parse::<Long>().is_ok(),
+        None => false,
+    }
+}
+
+pub fn is_uint(word: &str) -> bool {
+    match word.strip_suffix(['u', 'U']) {
+        Some(number) => number.parse::<UInt>().is_ok(),
+        None => false,
+    }
+}
+
+pub fn is_ulong(word: &str) -> bool {
+    match word.strip_suffix("ul").or_else(|| word.strip_suffix("UL")) {
+        Some(number) => number.parse::<ULong>().is_ok(),
+        None => false,
+    }
+}
+
+pub fn is_int128(word: &str) -> bool {
+    match word.strip_suffix("i128") {
+        Some(number) => number.parse::<Int128>().is_ok(),
+        None => false,
+    }
+}
+
+#[cfg(feature = "bigint")]
+pub fn is_bigint(word: &str) -> bool {
+    match word.strip_suffix('n') {
+        Some(number) => number.parse::<BigInt>().is_ok(),
+        None => false,
+    }
+}
+
+pub fn is_decimal(word: &str) -> bool {
+    match word.strip_suffix(['d', 'D']) {
+        Some(number) => number.parse::<Decimal>().is_ok(),
+        None => false,
+    }
+}
+
+pub fn is_float(word: &str) -> bool {
+    match word.strip_suffix(['f', 'F']) {
+        Some(number) => number.parse::<Float>().is_ok(),
+        None => false,
+    }
+}
+
+pub fn is_double(word: &str) -> bool {
+    word.parse::<Double>().is_ok()
+}
+
+pub fn is_byte(word: &str) -> bool {
+    match word.strip_prefix("0x") {
+        Some(number) => number.len() == 2 && number.bytes().all(|c| c.is_ascii_hexdigit()),
+        None => false,
+    }
+}
+
+// A single character in single quotes, 'a', or one of the escapes '\n',
+// '\t', '\r', '\0', '\\', '\'' and '\u{1F600}'. A space has to be written
+// as '\u{20}' because words are split at whitespace.
+pub fn parse_char(word: &str) -> Option<char> {
+    let inner = word.strip_prefix('\'')?.strip_suffix('\'')?;
+    let mut chars = inner.chars();
+
+    let escape = match (chars.next()?, chars.next()) {
+        (c, None) if c != '\\' && c != '\'' => return Some(c),
+        ('\\', Some(escape)) => escape,
+        _ => return None,
+    };
+
+    let rest = chars.as_str();
+    match (escape, rest) {
+        ('n', "") => Some('\n'),
+        ('t', "") => Some('\t'),
+        ('r', "") => Some('\r'),
+        ('0', "") => Some('\0'),
+        ('\\', "") => Some('\\'),
+        ('\'', "") => Some('\''),
+        ('u', _) => {
+            let hex = rest.strip_prefix('{')?.strip_suffix('}')?;
+            if hex.is_empty() || hex.len() > 6 || !hex.bytes().all(|c| c.is_ascii_hexdigit()) {
+                return None;
+            }
+            char::from_u32(u32::from_str_radix(hex, 16).ok()?)
+        }
+        _ => None,
+    }
+}
+
+pub fn is_char(word: &str) -> bool {
+    parse_char(word).is_some()
+}
+
+// Any of the number literals above
+pub fn is_number(word: &str) -> bool {
+    #[cfg(feature = "bigint")]
+    if is_bigint(word) {
+        return true;
+    }
+
+    is_int(word)
+        || is_long(word)
+        || is_uint(word)
+        || is_ulong(word)
+        || is_int128(word)
+        || is_float(word)
+        || is_double(word)
+        || is_byte(word)
+        || is_decimal(word)
+}
 
 pub fn int_number(s: &mut Engine) -> Result<String, String> {
     let number = s.get_curr_word().parse::<Int>().unwrap();
@@ -14,6 +136,47 @@ pub fn long_number(s: &mut Engine) -> Result<String, String> {
     Ok("".to_string())
 }
 
+pub fn uint_number(s: &mut Engine) -> Result<String, String> {
+    let mut word = s.get_curr_word();
+    word.pop();
+    let number = word.parse::<UInt>().unwrap();
+    s.main_stack.push(Types::UInt(number));
+    Ok("".to_string())
+}
+
+pub fn ulong_number(s: &mut Engine) -> Result<String, String> {
+    let mut word = s.get_curr_word();
+    word.truncate(word.len() - 2);
+    let number = word.parse::<ULong>().unwrap();
+    s.main_stack.push(Types::ULong(number));
+    Ok("".to_string())
+}
+
+pub fn int128_number(s: &mut Engine) -> Result<String, String> {
+    let mut word = s.get_curr_word();
+    word.truncate(word.len() - 4);
+    let number = word.parse::<Int128>().unwrap();
+    s.main_stack.push(Types::Int128(number));
+    Ok("".to_string())
+}
+
+#[cfg(feature = "bigint")]
+pub fn bigint_number(s: &mut Engine) -> Result<String, String> {
+    let mut word = s.get_curr_word();
+    word.pop();
+    let number = word.parse::<BigInt>().unwrap();
+    s.main_stack.push(Types::BigInt(number));
+    Ok("".to_string())
+}
+
+pub fn decimal_number(s: &mut Engine) -> Result<String, String> {
+    let mut word = s.get_curr_word();
+    word.pop();
+    let number = word.parse::<Decimal>().unwrap();
+    s.main_stack.push(Types::Decimal(number));
+    Ok("".to_string())
+}
+
 pub fn float_number(s: &mut Engine) -> Result<String, String> {
     let mut word = s.get_curr_word();
     word.pop();
@@ -28,6 +191,12 @@ pub fn double_number(s: &mut Engine) -> Result<String, String> {
     Ok("".to_string())
 }
 
+pub fn char_literal(s: &mut Engine) -> Result<String, String> {
+    let c = parse_char(&s.get_curr_word()).unwrap();
+    s.main_stack.push(Types::Char(c));
+    Ok("".to_string())
+}
+
 pub fn byte_number(s: &mut Engine) -> Result<String, String> {
     let mut word = s.get_curr_word();
     word.remove(0);
@@ -1,61 +1,133 @@
-use crate::prelude::{Engine, EngineMode};
+use super::effect_ops::check_effect;
+use crate::{
+    errors::{MISSING_WORD_NAME_ERROR, WORD_NAME_TAKEN_ERROR},
+    prelude::{CompiledWord, Engine, EngineMode, Location, StackEffect, WordName},
+};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::mem;
 
 pub fn start_compile(s: &mut Engine) -> Result<String, String> {
+    let location = s.get_curr_location().unwrap_or_else(|| unknown_location(s));
+
+    s.new_compiled_word = Some(CompiledWord {
+        name: String::new(),
+        body: Vec::new(),
+        locations: Vec::new(),
+        span: (location.clone(), location),
+    });
     s.mode = EngineMode::COMPILE;
     Ok("".to_string())
 }
 
 pub fn end_compile(s: &mut Engine) -> Result<String, String> {
-    s.new_compiled_word = s.new_compiled_word.trim().to_string();
-    let new_word_name = s
-        .new_compiled_word
-        .split_whitespace()
-        .next()
-        .unwrap()
-        .to_string();
+    s.mode = EngineMode::NORMAL;
+    let mut new_word = match s.new_compiled_word.take() {
+        Some(new_word) if !new_word.name.is_empty() => new_word,
+        _ => return Err(MISSING_WORD_NAME_ERROR.to_string()),
+    };
+    if let Some(location) = s.get_curr_location() {
+        new_word.span.1 = location;
+    }
+
+    let tokens: Vec<&str> = new_word.body.iter().map(String::as_str).collect();
+    if let Some((effect, len)) = StackEffect::parse(&tokens) {
+        check_effect(s, &new_word.name, &effect, &tokens[len..])?;
+    }
 
     let possibly_existing_word_position = s
         .compiled_words
         .iter()
-        .position(|compiled| compiled.starts_with(new_word_name.as_str()));
-    if possibly_existing_word_position.is_some() {
-        s.compiled_words[possibly_existing_word_position.unwrap()] = s.new_compiled_word.clone();
+        .position(|compiled| compiled.name == new_word.name);
+    if let Some(position) = possibly_existing_word_position {
+        s.compiled_words[position] = new_word;
+    } else {
+        s.compiled_words.push(new_word);
     }
 
-    s.compiled_words.push(s.new_compiled_word.clone());
-    s.new_compiled_word.clear();
-    s.mode = EngineMode::NORMAL;
-
     Ok("".to_string())
 }
 
 pub fn compile(s: &mut Engine) -> Result<String, String> {
-    s.new_compiled_word += " ";
-    s.new_compiled_word += s.get_curr_word().as_str();
+    let word = s.get_curr_word();
+    let location = s.get_curr_location().unwrap_or_else(|| unknown_location(s));
+
+    let naming = s
+        .new_compiled_word
+        .as_ref()
+        .is_some_and(|new_word| new_word.name.is_empty());
+    if naming && !is_free_name(s) {
+        s.mode = EngineMode::NORMAL;
+        s.new_compiled_word = None;
+        return Err(WORD_NAME_TAKEN_ERROR.to_string() + word.as_str());
+    }
+
+    if let Some(new_word) = s.new_compiled_word.as_mut() {
+        if new_word.name.is_empty() {
+            new_word.name = word;
+        } else {
+            new_word.body.push(word);
+            new_word.locations.push(location);
+        }
+    }
 
     Ok("".to_string())
 }
 
+// A custom word can't be named like a word the engine would run instead of
+// it, only custom words can be defined again
+fn is_free_name(s: &mut Engine) -> bool {
+    let word = s.get_curr_word();
+    runs_as(s, &word).is_none_or(|name| name == "<custom word>")
+}
+
+// Name of the first word the engine would run for `word` outside of a
+// definition, found by putting `word` in place of the current one for a moment
+pub fn runs_as(s: &mut Engine, word: &str) -> Option<WordName> {
+    let idx = *s.curr_word_idx.last().unwrap() as usize;
+    let token = mem::replace(
+        &mut s.curr_line_vec.last_mut().unwrap()[idx],
+        word.to_string(),
+    );
+    let mode = mem::replace(&mut s.mode, EngineMode::NORMAL);
+
+    let name = s.words.iter().find(|w| w.1(s)).map(|w| w.0);
+
+    s.mode = mode;
+    s.curr_line_vec.last_mut().unwrap()[idx] = token;
+    name
+}
+
+// Used for words executed outside of a loaded source
+fn unknown_location(s: &Engine) -> Location {
+    Location {
+        source: s.source.clone(),
+        line: 0,
+        column: 0,
+    }
+}
+
+pub fn is_compiled_word(s: &Engine) -> bool {
+    let word = s.get_curr_word();
+    s.compiled_words
+        .iter()
+        .any(|compiled| compiled.name == word)
+}
+
 pub fn run_compiled(s: &mut Engine) -> Result<String, String> {
+    let word = s.get_curr_word();
     let compiled_word = s
         .compiled_words
         .iter()
-        .find(|compiled| compiled.starts_with(s.get_curr_word().as_str()))
+        .find(|compiled| compiled.name == word)
         .unwrap();
 
-    let mut word_copy = compiled_word.clone();
+    let body = compiled_word.body.clone();
+    let locations = compiled_word.locations.clone();
 
-    for _ in 0..s.get_curr_word().len() {
-        word_copy.remove(0);
-    }
+    s.push_frame(body, locations, true)?;
 
-    s.compiled_exec.push(true);
-    let ret = s.eval(word_copy);
-    if s.compiled_exec.len() == 1 {
-        *s.compiled_exec.last_mut().unwrap() = false;
-    } else {
-        s.compiled_exec.pop();
-    }
-
-    ret
+    Ok("".to_string())
 }
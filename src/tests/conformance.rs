@@ -0,0 +1,1194 @@
+// Conformance suite for the words of `Standard`
+//
+// Every case is evaluated in a fresh engine, a program can span several
+// `eval` calls separated by newlines. The stack, the joined output and the
+// status after the last line are compared, or the prefix of the error it
+// failed with. `every_word_is_covered` makes sure each word registered in
+// `Standard::new` is executed by at least one case.
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
+
+use crate::prelude::{
+    Engine, EvalStatus, MapKey, Standard, Struct, Types, VarRef, WordList,
+    CONDITIONAL_STACK_UNDERFLOW_ERROR, DECIMAL_SCALE_ERROR, DIVISION_BY_ZERO_ERROR,
+    INDEX_OUT_OF_RANGE_ERROR, INVALID_CODE_POINT_ERROR, INVALID_TYPE_ERROR, LIST_SYNTAX_ERROR,
+    LOOP_INCREMENT_ERROR, LOOP_STACK_UNDERFLOW_ERROR, MISSING_VARIABLE_NAME_ERROR,
+    MISSING_WORD_NAME_ERROR, NOT_A_FLAG_ERROR, OVERFLOW_ERROR, SHIFT_AMOUNT_ERROR,
+    STACK_UNDERFLOW_ERROR, STRUCT_SYNTAX_ERROR, TEST_FAILED_ERROR, TEST_SYNTAX_ERROR,
+    TYPE_CONVERSION_FAILURE_ERROR, UNBALANCED_LOOP_ERROR, UNKNOWN_WORD_ERROR,
+    VARIABLE_INDEX_OUT_OR_RANGE_ERROR, VARIABLE_NOT_DEFINED, WORD_NAME_TAKEN_ERROR,
+};
+
+use Types::{Bool, Byte, Char, Double, Float, Int, Int128, List, Long, UInt, ULong};
+
+fn s(val: &str) -> Types {
+    Types::Str(val.to_string())
+}
+
+fn dec(val: &str) -> Types {
+    Types::Decimal(val.parse().unwrap())
+}
+
+fn var(index: usize, name: &str) -> Types {
+    Types::VarRef(VarRef {
+        index,
+        name: name.to_string(),
+    })
+}
+
+fn point(x: Types, y: Types) -> Types {
+    Types::Struct(Struct {
+        name: "point".to_string(),
+        fields: vec![("x".to_string(), x), ("y".to_string(), y)],
+    })
+}
+
+fn map<const N: usize>(entries: [(MapKey, Types); N]) -> Types {
+    Types::Map(BTreeMap::from(entries))
+}
+
+struct Case {
+    program: &'static str,
+    stack: Vec<Types>,
+    output: &'static str,
+    status: EvalStatus,
+    error: Option<&'static str>,
+}
+
+fn case(program: &'static str, stack: Vec<Types>, output: &'static str) -> Case {
+    Case {
+        program,
+        stack,
+        output,
+        status: EvalStatus::Completed,
+        error: None,
+    }
+}
+
+fn fails(program: &'static str, error: &'static str) -> Case {
+    Case {
+        program,
+        stack: Vec::new(),
+        output: "",
+        status: EvalStatus::Completed,
+        error: Some(error),
+    }
+}
+
+// Checked math that overflows fails, with the bigint feature it gives a BigInt
+#[cfg(not(feature = "bigint"))]
+fn overflows(program: &'static str, _result: &'static str) -> Case {
+    fails(program, OVERFLOW_ERROR)
+}
+
+#[cfg(feature = "bigint")]
+fn overflows(program: &'static str, result: &'static str) -> Case {
+    case(program, vec![Types::BigInt(result.parse().unwrap())], "")
+}
+
+impl Case {
+    fn status(mut self, status: EvalStatus) -> Case {
+        self.status = status;
+        self
+    }
+}
+
+fn cases() -> Vec<Case> {
+    let cases = vec![
+        // Comments
+        case("1 ( 2 ) 3", vec![Int(1), Int(3)], ""),
+        case("1 ( 2 3", vec![Int(1)], "").status(EvalStatus::Pending),
+        // Conditionals
+        case(": t if 1 else 2 then ; -1 t 0 t", vec![Int(1), Int(2)], ""),
+        case(": t if 1 then 3 ; 0 t", vec![Int(3)], ""),
+        case(
+            ": t if if 1 else 2 then else 3 then ; -1 -1 t 0 -1 t 0 t",
+            vec![Int(1), Int(2), Int(3)],
+            "",
+        ),
+        // Any integer is a flag, only zero is false
+        case(
+            ": t if 1 else 2 then ; true t false t 5 t 0x00 t -3l t",
+            vec![Int(1), Int(2), Int(1), Int(2), Int(1)],
+            "",
+        ),
+        fails(": t if 1 then ; t", STACK_UNDERFLOW_ERROR),
+        fails(": t if 1 then ; 1.5 t", NOT_A_FLAG_ERROR),
+        fails(": t if 1 then ; \" a \" t", NOT_A_FLAG_ERROR),
+        fails(": t then ; t", CONDITIONAL_STACK_UNDERFLOW_ERROR),
+        fails(": t else ; t", CONDITIONAL_STACK_UNDERFLOW_ERROR),
+        fails("-1 if 1 then", UNKNOWN_WORD_ERROR),
+        // Loops
+        case(": t 3 0 for i next ; t", vec![Int(0), Int(1), Int(2)], ""),
+        case(
+            ": t 10 0 for i 3 bynext ; t",
+            vec![Int(0), Int(3), Int(6), Int(9)],
+            "",
+        ),
+        case(
+            ": t 3 while dup 0 != do dup 1 - again ; t",
+            vec![Int(3), Int(2), Int(1), Int(0)],
+            "",
+        ),
+        case(": t 0 do 1 again 2 ; t", vec![Int(2)], ""),
+        case(": t false do 1 again 2 ; t", vec![Int(2)], ""),
+        fails(": t 0 for next ; t", STACK_UNDERFLOW_ERROR),
+        fails(": t 1 0l for next ; t", INVALID_TYPE_ERROR),
+        fails(": t next ; t", LOOP_STACK_UNDERFLOW_ERROR),
+        fails(": t 1 bynext ; t", LOOP_STACK_UNDERFLOW_ERROR),
+        fails(": t 2 0 for 1.0 bynext ; t", INVALID_TYPE_ERROR),
+        fails(": t 2 0 for 0 bynext ; t", LOOP_INCREMENT_ERROR),
+        fails(": t 2 0 for -1 bynext ; t", LOOP_INCREMENT_ERROR),
+        fails(": t i ; t", LOOP_STACK_UNDERFLOW_ERROR),
+        fails(": t do again ; t", STACK_UNDERFLOW_ERROR),
+        fails(": t 1.0 do again ; t", NOT_A_FLAG_ERROR),
+        fails(": t again ; t", UNBALANCED_LOOP_ERROR),
+        fails(": t 0 do ; t", UNBALANCED_LOOP_ERROR),
+        fails(": a next ; : t 2 0 for a ; t", UNBALANCED_LOOP_ERROR),
+        fails(": a 1 bynext ; : t 2 0 for a ; t", UNBALANCED_LOOP_ERROR),
+        fails(": t 2 0 for ; t", UNBALANCED_LOOP_ERROR),
+        fails(": a 2 0 for ; : t a 1 ; t", UNBALANCED_LOOP_ERROR),
+        // Variables
+        case(
+            "let a @a 1 push @a 2 push @a len @a 0 get @a pop",
+            vec![Int(2), Int(1), Int(2)],
+            "",
+        ),
+        case(
+            "let a @a 1 push @a 0 \" x \" set @a 0 get",
+            vec![s("x")],
+            "",
+        ),
+        case("let a let b @b", vec![var(1, "b")], ""),
+        case("let a @a 1 push let a @a len", vec![Int(0)], ""),
+        case("let a @a . @a to_str", vec![s("@a")], "@a"),
+        // Declaring a variable again keeps the handles to it and to later ones
+        case(
+            "let a let b @a @b let a 2 push 1 push @a 0 get @b 0 get",
+            vec![Int(1), Int(2)],
+            "",
+        ),
+        case("let a let b @a @b == @a @a ==", vec![Bool(false), Bool(true)], ""),
+        fails("@b", VARIABLE_NOT_DEFINED),
+        fails("let a @a 0 get", VARIABLE_INDEX_OUT_OR_RANGE_ERROR),
+        fails("let a @a 0 1 set", VARIABLE_INDEX_OUT_OR_RANGE_ERROR),
+        fails("let a @a pop", STACK_UNDERFLOW_ERROR),
+        fails("let a 1.5 pop", INVALID_TYPE_ERROR),
+        fails("push", STACK_UNDERFLOW_ERROR),
+        fails("let a 0 len", INVALID_TYPE_ERROR),
+        fails("let a 0 1 push", INVALID_TYPE_ERROR),
+        fails("let a 0 0 get", INVALID_TYPE_ERROR),
+        fails("let a 0 0 1 set", INVALID_TYPE_ERROR),
+        fails("let a @a to_int", INVALID_TYPE_ERROR),
+        fails("let", MISSING_VARIABLE_NAME_ERROR),
+        fails("len", STACK_UNDERFLOW_ERROR),
+        fails("let a @a \" x \" get", INVALID_TYPE_ERROR),
+        // Indices are integers of any type
+        case(
+            "let a @a 1 push @a 0u 2 set @a 0l get @a 0x00 get",
+            vec![Int(2), Int(2)],
+            "",
+        ),
+        fails("let a @a 1 push @a 0.0 get", INVALID_TYPE_ERROR),
+        fails("let a @a 1 push @a -1.5 get", INVALID_TYPE_ERROR),
+        fails("let a @a 1 push @a 0.0d 2 set", INVALID_TYPE_ERROR),
+        fails("let a @a 1 push @a -1 get", VARIABLE_INDEX_OUT_OR_RANGE_ERROR),
+        fails("let a @a 1 push @a -1l 2 set", VARIABLE_INDEX_OUT_OR_RANGE_ERROR),
+        // Math
+        case("2 3 +", vec![Int(5)], ""),
+        case("2 3 -", vec![Int(-1)], ""),
+        case("2 3 *", vec![Int(6)], ""),
+        case("7 2 /", vec![Int(3)], ""),
+        case("7 2 %", vec![Int(1)], ""),
+        case("-7 2 %", vec![Int(-1)], ""),
+        case(
+            "-3 abs -3l abs -3f abs -3.5 abs",
+            vec![Int(3), Long(3), Float(3.0), Double(3.5)],
+            "",
+        ),
+        case("\" a \" \" b \" +", vec![s("a b")], ""),
+        fails("1 +", STACK_UNDERFLOW_ERROR),
+        fails("-", STACK_UNDERFLOW_ERROR),
+        fails("1 *", STACK_UNDERFLOW_ERROR),
+        fails("1 /", STACK_UNDERFLOW_ERROR),
+        fails("1 %", STACK_UNDERFLOW_ERROR),
+        fails("1 0 /", DIVISION_BY_ZERO_ERROR),
+        fails("1l 0x00 /", DIVISION_BY_ZERO_ERROR),
+        fails("1 0l %", DIVISION_BY_ZERO_ERROR),
+        case("1.0 0 /", vec![Double(f64::INFINITY)], ""),
+        overflows("2147483647 1 +", "2147483648"),
+        overflows("-9223372036854775808l 1 -", "-9223372036854775809"),
+        overflows("0xff 0x02 *", "510"),
+        overflows("0x00 0x01 -", "-1"),
+        overflows("-2147483648 -1 /", "2147483648"),
+        overflows("-2147483648 -1 %", "0"),
+        overflows("-2147483648 abs", "2147483648"),
+        case(
+            "wrapping 2147483647 1 + 0xff 0x02 * -2147483648 -1 / -2147483648 abs",
+            vec![Int(-2147483648), Byte(254), Int(-2147483648), Int(-2147483648)],
+            "",
+        ),
+        case(
+            "saturating 2147483647 1 + 0x00 0x01 - -2147483648 -1 / -2147483648 -1 % -2147483648 abs",
+            vec![Int(2147483647), Byte(0), Int(2147483647), Int(0), Int(2147483647)],
+            "",
+        ),
+        overflows("wrapping checked 2147483647 1 +", "2147483648"),
+        case(
+            "4294967295u 1 + 3000000000u 2u / -1 1u + 1ul 2i128 -",
+            vec![Long(4294967296), UInt(1500000000), Long(0), Int128(-1)],
+            "",
+        ),
+        overflows("4294967295u 1u +", "4294967296"),
+        overflows("0ul 1ul -", "-1"),
+        overflows("170141183460469231731687303715884105727i128 1 +", "170141183460469231731687303715884105728"),
+        fails("1u 0u %", DIVISION_BY_ZERO_ERROR),
+        case(
+            "wrapping 0u 1u - saturating 0ul 1ul - -5i128 abs",
+            vec![UInt(u32::MAX), ULong(0), Int128(5)],
+            "",
+        ),
+        case(
+            "7u 3u min 1ul 2i128 max 0u negate saturating 5ul negate",
+            vec![UInt(3), Int128(2), UInt(0), ULong(0)],
+            "",
+        ),
+        overflows("1u negate", "-1"),
+        case(
+            "0x05 abs 5u abs 5ul abs",
+            vec![Byte(5), UInt(5), ULong(5)],
+            "",
+        ),
+        // Decimals keep the digits after the point exactly
+        case(
+            "0.1d 0.2d + . 1.5d 2 * . 1d 3d / . 2d 3d / . 1.50d 0.25d * . 7.5d 2d % . -7.5d 2 / . 12.50d .",
+            vec![],
+            "0.3 3.0 0.333333333333333333 0.666666666666666667 0.3750 1.5 -3.75 12.50",
+        ),
+        case(
+            "2.345d 2 rescale . 2.355d 2 rescale . -2.345d 2 rescale . 5 3 rescale .",
+            vec![],
+            "2.35 2.36 -2.35 5.000",
+        ),
+        case(
+            "-1.5d abs 1.5d negate 1.5d 2 min 0.1d 0.2d max 1.5d 1i128 + 0.5d 0.25f +",
+            vec![dec("1.5"), dec("-1.5"), dec("1.5"), dec("0.2"), Double(2.5), Double(0.75)],
+            "",
+        ),
+        // Decimals always fail on overflow, whatever the arithmetic mode
+        fails("170141183460469231731687303715884105727d 1d +", OVERFLOW_ERROR),
+        fails("wrapping 170141183460469231731687303715884105727d 10 *", OVERFLOW_ERROR),
+        fails("1d 0d /", DIVISION_BY_ZERO_ERROR),
+        fails("1.5d 0 %", DIVISION_BY_ZERO_ERROR),
+        fails("1d 19 rescale", DECIMAL_SCALE_ERROR),
+        fails("1d -1 rescale", DECIMAL_SCALE_ERROR),
+        fails("1d 1.0 rescale", INVALID_TYPE_ERROR),
+        fails("\" a \" 2 rescale", INVALID_TYPE_ERROR),
+        fails("1 rescale", STACK_UNDERFLOW_ERROR),
+        fails("1d 1 band", INVALID_TYPE_ERROR),
+        case(
+            "2 3l min 2 3l max 1.5 2 min 0x01 0x02 max",
+            vec![Long(2), Long(3), Double(1.5), Byte(2)],
+            "",
+        ),
+        case(
+            "5 negate 2.5f negate -1l negate 0x00 negate",
+            vec![Int(-5), Float(-2.5), Long(1), Byte(0)],
+            "",
+        ),
+        case(
+            "wrapping 0x01 negate saturating -2147483648 negate",
+            vec![Byte(255), Int(2147483647)],
+            "",
+        ),
+        overflows("-2147483648 negate", "2147483648"),
+        overflows("0x01 negate", "-1"),
+        fails("1 min", STACK_UNDERFLOW_ERROR),
+        fails("max", STACK_UNDERFLOW_ERROR),
+        fails("negate", STACK_UNDERFLOW_ERROR),
+        fails("1 \" a \" min", INVALID_TYPE_ERROR),
+        fails("\" a \" negate", INVALID_TYPE_ERROR),
+        // Floating point functions
+        case(
+            "4 sqrt 2.25f sqrt 2 10 pow 0.5f 0x02 pow",
+            vec![Double(2.0), Float(1.5), Double(1024.0), Float(0.25)],
+            "",
+        ),
+        case(
+            "0 exp 1 ln 1000l log10",
+            vec![Double(1.0), Double(0.0), Double(3.0)],
+            "",
+        ),
+        case(
+            "0 sin 0 cos 0.0f tan 0 1 atan2 3 4 hypot",
+            vec![Double(0.0), Double(1.0), Float(0.0), Double(0.0), Double(5.0)],
+            "",
+        ),
+        case(
+            "1.5 floor 1.5f ceil 2.5 round -1.5 trunc 7 floor 7l round",
+            vec![Double(1.0), Float(2.0), Double(3.0), Double(-1.0), Int(7), Long(7)],
+            "",
+        ),
+        case(
+            "pi e",
+            vec![Double(std::f64::consts::PI), Double(std::f64::consts::E)],
+            "",
+        ),
+        case("-1 sqrt to_str", vec![s("NaN")], ""),
+        fails("sqrt", STACK_UNDERFLOW_ERROR),
+        fails("1 pow", STACK_UNDERFLOW_ERROR),
+        fails("atan2", STACK_UNDERFLOW_ERROR),
+        fails("\" a \" floor", INVALID_TYPE_ERROR),
+        fails("\" a \" 2 hypot", INVALID_TYPE_ERROR),
+        case("2147483647 1.0 +", vec![Double(2147483648.0)], ""),
+        case("4u 2.0f pow 9ul sqrt", vec![Double(16.0), Double(3.0)], ""),
+        fails("abs", STACK_UNDERFLOW_ERROR),
+        fails("1 \" a \" +", INVALID_TYPE_ERROR),
+        fails("\" a \" \" b \" -", INVALID_TYPE_ERROR),
+        fails("\" a \" 2 *", INVALID_TYPE_ERROR),
+        fails("\" a \" 2 /", INVALID_TYPE_ERROR),
+        fails("2 \" a \" %", INVALID_TYPE_ERROR),
+        fails("\" a \" abs", INVALID_TYPE_ERROR),
+        // Logic
+        case("true false", vec![Bool(true), Bool(false)], ""),
+        case("true . false .", vec![], "true false"),
+        case("1 1 == 1 2 ==", vec![Bool(true), Bool(false)], ""),
+        case("1 1 != 1 2 !=", vec![Bool(false), Bool(true)], ""),
+        case(
+            "true true == true false != false true <",
+            vec![Bool(true), Bool(true), Bool(false)],
+            "",
+        ),
+        case(
+            "true true and true false and false false and -1 5l and",
+            vec![Bool(true), Bool(false), Bool(false), Bool(true)],
+            "",
+        ),
+        case(
+            "true true or true false or false false or 0 0x00 or",
+            vec![Bool(true), Bool(true), Bool(false), Bool(false)],
+            "",
+        ),
+        case(
+            "true not false not -1 not 0 not 2u not",
+            vec![Bool(false), Bool(true), Bool(false), Bool(true), Bool(false)],
+            "",
+        ),
+        // Comparisons check whether the top value is greater (smaller) than
+        // the one below it
+        case("2 3 > 3 2 >", vec![Bool(true), Bool(false)], ""),
+        case("2 3 < 3 2 <", vec![Bool(false), Bool(true)], ""),
+        case(
+            "\" a \" \" a \" == \" a \" \" b \" !=",
+            vec![Bool(true), Bool(true)],
+            "",
+        ),
+        case(
+            "\" a \" \" b \" > \" a \" \" b \" <",
+            vec![Bool(true), Bool(false)],
+            "",
+        ),
+        fails("1 ==", STACK_UNDERFLOW_ERROR),
+        fails("!=", STACK_UNDERFLOW_ERROR),
+        fails("-1 and", STACK_UNDERFLOW_ERROR),
+        fails("-1 or", STACK_UNDERFLOW_ERROR),
+        fails("not", STACK_UNDERFLOW_ERROR),
+        fails("1 >", STACK_UNDERFLOW_ERROR),
+        fails("1 <", STACK_UNDERFLOW_ERROR),
+        fails("1 \" a \" ==", INVALID_TYPE_ERROR),
+        fails("\" a \" 1 !=", INVALID_TYPE_ERROR),
+        fails("true -1 ==", INVALID_TYPE_ERROR),
+        fails("-1 \" a \" and", NOT_A_FLAG_ERROR),
+        fails("\" a \" 0 and", NOT_A_FLAG_ERROR),
+        fails("\" a \" true or", NOT_A_FLAG_ERROR),
+        fails("true \" a \" or", NOT_A_FLAG_ERROR),
+        fails("-1.0 0 or", NOT_A_FLAG_ERROR),
+        fails("1.5d not", NOT_A_FLAG_ERROR),
+        fails("1 \" a \" >", INVALID_TYPE_ERROR),
+        fails("\" a \" 1 <", INVALID_TYPE_ERROR),
+        case(
+            "4294967295u -1 > 18446744073709551615ul -1 < 1ul 1i128 == 2u 2.0f !=",
+            vec![Bool(false), Bool(true), Bool(true), Bool(false)],
+            "",
+        ),
+        // Bit manipulation
+        case(
+            "12 10 band 12 10 bor 12 10 bxor 0x0f 0xf0 bor 0x0f 256 band",
+            vec![Int(8), Int(14), Int(6), Byte(255), Int(0)],
+            "",
+        ),
+        case(
+            "0 binvert 0x0f binvert 0l binvert",
+            vec![Int(-1), Byte(240), Long(-1)],
+            "",
+        ),
+        case(
+            "1 4 lshift 1 32 lshift 0x81 1 lshift 1l 40 lshift",
+            vec![Int(16), Int(0), Byte(2), Long(1 << 40)],
+            "",
+        ),
+        case(
+            "-16 2 rshift -16 2 arshift 0x80 7 arshift 0x80 7 rshift -1 100 arshift -1l 63 rshift",
+            vec![Int(1073741820), Int(-4), Byte(255), Byte(1), Int(-1), Long(1)],
+            "",
+        ),
+        case(
+            "0x81 1 rotl 1 1 rotr 1l 65 rotl",
+            vec![Byte(3), Int(i32::MIN), Long(2)],
+            "",
+        ),
+        case(
+            "0u binvert 12u 10ul band 1i128 100 lshift 2147483648u 31 arshift 4294967295u 4 rshift",
+            vec![UInt(u32::MAX), ULong(8), Int128(1 << 100), UInt(u32::MAX), UInt(0x0fffffff)],
+            "",
+        ),
+        case(
+            "-1i128 1 rshift 1ul 64u rotl 1u 1i128 rotr",
+            vec![Int128(i128::MAX), ULong(1), UInt(1 << 31)],
+            "",
+        ),
+        case(
+            "255 popcount -1l popcount 0x0f popcount",
+            vec![Int(8), Int(64), Int(4)],
+            "",
+        ),
+        case(
+            "-1i128 popcount 255u popcount 3ul popcount",
+            vec![Int(128), Int(8), Int(2)],
+            "",
+        ),
+        fails("1 band", STACK_UNDERFLOW_ERROR),
+        fails("bor", STACK_UNDERFLOW_ERROR),
+        fails("1 bxor", STACK_UNDERFLOW_ERROR),
+        fails("binvert", STACK_UNDERFLOW_ERROR),
+        fails("popcount", STACK_UNDERFLOW_ERROR),
+        fails("1 lshift", STACK_UNDERFLOW_ERROR),
+        fails("1 rotr", STACK_UNDERFLOW_ERROR),
+        fails("1.5 1 band", INVALID_TYPE_ERROR),
+        fails("1 1.5f bor", INVALID_TYPE_ERROR),
+        fails("\" a \" 1 bxor", INVALID_TYPE_ERROR),
+        fails("1.0 binvert", INVALID_TYPE_ERROR),
+        fails("\" a \" popcount", INVALID_TYPE_ERROR),
+        fails("1.0 1 lshift", INVALID_TYPE_ERROR),
+        fails("1 1.0 rshift", INVALID_TYPE_ERROR),
+        fails("1 -1 arshift", SHIFT_AMOUNT_ERROR),
+        fails("1 -1l rotl", SHIFT_AMOUNT_ERROR),
+        // Stack
+        case(
+            "1 2l 3f 4.5 0x05 \" six \" . . . . . .",
+            vec![],
+            "six 5 4.5 3 2 1",
+        ),
+        case("7 peek", vec![Int(7)], "7"),
+        case("1 dup", vec![Int(1), Int(1)], ""),
+        case("1 2 2dup", vec![Int(1), Int(2), Int(1), Int(2)], ""),
+        case("1 2 drop", vec![Int(1)], ""),
+        case("drop", vec![], ""),
+        case("1 2 swap", vec![Int(2), Int(1)], ""),
+        case("1 2 3 rot", vec![Int(2), Int(3), Int(1)], ""),
+        fails(".", STACK_UNDERFLOW_ERROR),
+        fails("peek", STACK_UNDERFLOW_ERROR),
+        fails("dup", STACK_UNDERFLOW_ERROR),
+        fails("1 2dup", STACK_UNDERFLOW_ERROR),
+        fails("1 swap", STACK_UNDERFLOW_ERROR),
+        fails("1 2 rot", STACK_UNDERFLOW_ERROR),
+        // Literals
+        case(
+            "12 -12 12l 12L 1.5f 1.5F 1.5 12e1 0x1f",
+            vec![
+                Int(12),
+                Int(-12),
+                Long(12),
+                Long(12),
+                Float(1.5),
+                Float(1.5),
+                Double(1.5),
+                Double(120.0),
+                Byte(31),
+            ],
+            "",
+        ),
+        case("3000000000", vec![Double(3000000000.0)], ""),
+        fails("0x1", UNKNOWN_WORD_ERROR),
+        fails("0xzz", UNKNOWN_WORD_ERROR),
+        fails("0x+f", UNKNOWN_WORD_ERROR),
+        fails("12x", UNKNOWN_WORD_ERROR),
+        // Definitions
+        case(": sq dup * ; 3 sq", vec![Int(9)], ""),
+        case(": a 1 ; : a 2 ; a", vec![Int(2)], ""),
+        case(": a 1 ; : ab 2 ; ab a", vec![Int(2), Int(1)], ""),
+        case(": a 1\n2 ;\na", vec![Int(1), Int(2)], "").status(EvalStatus::Completed),
+        case(": a 1", vec![], "").status(EvalStatus::Compiling),
+        fails(": ;", MISSING_WORD_NAME_ERROR),
+        fails(": dup 1 ;", WORD_NAME_TAKEN_ERROR),
+        fails(": e 2 ; e", WORD_NAME_TAKEN_ERROR),
+        fails(": 12 1 ;", WORD_NAME_TAKEN_ERROR),
+        fails("sq", UNKNOWN_WORD_ERROR),
+        // Io
+        case("1 bye 2", vec![Int(1), Int(2)], "").status(EvalStatus::Exited),
+        case(": b bye ; b", vec![], ""),
+        case("1 . nl 2 .", vec![], "1 \n 2"),
+        case("0x48 emit 0x69 emit", vec![], "H i"),
+        case("-1 silent true silent false silent", vec![], ""),
+        case("\" name? \" input", vec![], "name?").status(EvalStatus::AwaitingInput),
+        case("\" name? \" input\nbob", vec![s("bob")], "name?"),
+        fails("emit", STACK_UNDERFLOW_ERROR),
+        case(
+            "'h' emit 105 emit '\\u{1F600}' emit 233u emit",
+            vec![],
+            "h i \u{1F600} \u{e9}",
+        ),
+        fails("55296 emit", INVALID_CODE_POINT_ERROR),
+        fails("1.5 emit", INVALID_TYPE_ERROR),
+        fails("silent", STACK_UNDERFLOW_ERROR),
+        fails("1.0 silent", NOT_A_FLAG_ERROR),
+        fails("input", STACK_UNDERFLOW_ERROR),
+        fails("1 input", INVALID_TYPE_ERROR),
+        // Conversions
+        case(
+            "1.9 to_int 7l to_int 0x07 to_int \" 42 \" to_int",
+            vec![Int(1), Int(7), Int(7), Int(42)],
+            "",
+        ),
+        case(
+            "1 to_long 2.5 to_long \" -3 \" to_long",
+            vec![Long(1), Long(2), Long(-3)],
+            "",
+        ),
+        case(
+            "1 to_float 2.5 to_float \" 0.5 \" to_float",
+            vec![Float(1.0), Float(2.5), Float(0.5)],
+            "",
+        ),
+        case(
+            "1 to_double 2.5f to_double \" 0.25 \" to_double",
+            vec![Double(1.0), Double(2.5), Double(0.25)],
+            "",
+        ),
+        case(
+            "65 to_byte 255l to_byte \" 7 \" to_byte",
+            vec![Byte(65), Byte(255), Byte(7)],
+            "",
+        ),
+        case(
+            "1 to_str 2l to_str 1.5f to_str 2.5 to_str 0x0a to_str \" x \" to_str",
+            vec![s("1"), s("2"), s("1.5"), s("2.5"), s("10"), s("x")],
+            "",
+        ),
+        case(
+            "4294967295l to_uint 7l to_uint \" 42 \" to_uint",
+            vec![UInt(u32::MAX), UInt(7), UInt(42)],
+            "",
+        ),
+        case(
+            "7 to_ulong 2.5 to_ulong \" 18446744073709551615 \" to_ulong",
+            vec![ULong(7), ULong(2), ULong(u64::MAX)],
+            "",
+        ),
+        case(
+            "18446744073709551615ul to_int128 -1l to_int128 \" -5 \" to_int128",
+            vec![Int128(u64::MAX as i128), Int128(-1), Int128(-5)],
+            "",
+        ),
+        case(
+            "7u to_str 8ul to_str -9i128 to_str 7u to_int 8ul to_long 9i128 to_double",
+            vec![s("7"), s("8"), s("-9"), Int(7), Long(8), Double(9.0)],
+            "",
+        ),
+        fails("to_int", STACK_UNDERFLOW_ERROR),
+        fails("to_long", STACK_UNDERFLOW_ERROR),
+        fails("to_float", STACK_UNDERFLOW_ERROR),
+        fails("to_double", STACK_UNDERFLOW_ERROR),
+        fails("to_byte", STACK_UNDERFLOW_ERROR),
+        fails("to_str", STACK_UNDERFLOW_ERROR),
+        case(
+            "true to_int false to_int true to_str",
+            vec![Int(-1), Int(0), s("true")],
+            "",
+        ),
+        case(
+            "true to_long false to_float true to_double true to_int128 true to_decimal false to_byte false to_uint false to_ulong",
+            vec![
+                Long(-1),
+                Float(0.0),
+                Double(-1.0),
+                Int128(-1),
+                dec("-1"),
+                Byte(0),
+                UInt(0),
+                ULong(0),
+            ],
+            "",
+        ),
+        fails("true to_byte", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("true to_uint", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("true to_ulong", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("\" x \" to_int", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("\" x \" to_long", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("\" x \" to_float", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("\" x \" to_double", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("\" 300 \" to_byte", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("to_uint", STACK_UNDERFLOW_ERROR),
+        fails("to_ulong", STACK_UNDERFLOW_ERROR),
+        fails("to_int128", STACK_UNDERFLOW_ERROR),
+        fails("\" -1 \" to_uint", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("\" -1 \" to_ulong", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("\" x \" to_int128", TYPE_CONVERSION_FAILURE_ERROR),
+        // Values out of the range of the target type don't convert
+        fails("300 to_byte", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("-1 to_byte", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("-1 to_uint", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("-1l to_ulong", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("2147483648l to_int", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("4294967295u to_int", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("18446744073709551615ul to_long", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("-1i128 to_ulong", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("1e20 to_int", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("-1e20 to_long", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("1e40 to_int128", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("-1.5 to_uint", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("256.0f to_byte", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("-1 sqrt to_int", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("300.5d to_byte", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("-0.5d 2 * to_uint", TYPE_CONVERSION_FAILURE_ERROR),
+        case(
+            "-0.5 to_uint 255.9 to_byte -2147483648.0 to_int 2.5d to_byte",
+            vec![UInt(0), Byte(255), Int(i32::MIN), Byte(2)],
+            "",
+        ),
+        case(
+            "12.75d to_int 12.75d to_str -1.5d to_double 0.1 to_decimal 7u to_decimal \" -12.50 \" to_decimal",
+            vec![Int(12), s("12.75"), Double(-1.5), dec("0.1"), dec("7"), dec("-12.50")],
+            "",
+        ),
+        fails("to_decimal", STACK_UNDERFLOW_ERROR),
+        fails("1.0 0 / to_decimal", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("\" 1.2.3 \" to_decimal", TYPE_CONVERSION_FAILURE_ERROR),
+        // Strings
+        case("\" hello   world \"", vec![s("hello world")], ""),
+        case("\" a \" \" b \" concat", vec![s("ab")], ""),
+        case(
+            "\" a,b,c \" \" , \" split",
+            vec![s("a"), s("b"), s("c")],
+            "",
+        ),
+        case("\" a b \" wsplit", vec![s("a"), s("b")], ""),
+        fails("\" a \" concat", STACK_UNDERFLOW_ERROR),
+        fails("\" a \" 1 concat", INVALID_TYPE_ERROR),
+        fails("\" a \" split", STACK_UNDERFLOW_ERROR),
+        fails("1 \" , \" split", INVALID_TYPE_ERROR),
+        fails("wsplit", STACK_UNDERFLOW_ERROR),
+        fails("1 wsplit", INVALID_TYPE_ERROR),
+        // Characters
+        case(
+            "'a' '\\u{1F600}' '\\n' '\\'' '\\\\' 'é' 'a' .",
+            vec![Char('a'), Char('\u{1F600}'), Char('\n'), Char('\''), Char('\\'), Char('é')],
+            "a",
+        ),
+        fails("'ab'", UNKNOWN_WORD_ERROR),
+        fails("'\\u{d800}'", UNKNOWN_WORD_ERROR),
+        case(
+            "\" héllo \" chars",
+            vec![Char('h'), Char('é'), Char('l'), Char('l'), Char('o')],
+            "",
+        ),
+        case(
+            "'a' char>int 97 int>char 0x41 int>char 'a' 'b' > 'a' 'a' == 'a' to_str",
+            vec![Int(97), Char('a'), Char('A'), Bool(true), Bool(true), s("a")],
+            "",
+        ),
+        case(
+            "'a' alpha? 'ж' alpha? '1' alpha? '7' digit? 'x' digit? '\\t' space? '\\u{3000}' space? 'a' space?",
+            vec![Bool(true), Bool(true), Bool(false), Bool(true), Bool(false), Bool(true), Bool(true), Bool(false)],
+            "",
+        ),
+        // Characters that change into several give a string
+        case(
+            "'é' upper 'A' lower 'ß' upper \" Grüße \" upper \" ÀB \" lower",
+            vec![Char('É'), Char('a'), s("SS"), s("GRÜSSE"), s("àb")],
+            "",
+        ),
+        fails("chars", STACK_UNDERFLOW_ERROR),
+        fails("'a' chars", INVALID_TYPE_ERROR),
+        fails("char>int", STACK_UNDERFLOW_ERROR),
+        fails("97 char>int", INVALID_TYPE_ERROR),
+        fails("int>char", STACK_UNDERFLOW_ERROR),
+        fails("-1 int>char", INVALID_CODE_POINT_ERROR),
+        fails("1114112 int>char", INVALID_CODE_POINT_ERROR),
+        fails("\" a \" int>char", INVALID_TYPE_ERROR),
+        fails("alpha?", STACK_UNDERFLOW_ERROR),
+        fails("digit?", STACK_UNDERFLOW_ERROR),
+        fails("space?", STACK_UNDERFLOW_ERROR),
+        fails("\" a \" alpha?", INVALID_TYPE_ERROR),
+        fails("upper", STACK_UNDERFLOW_ERROR),
+        fails("lower", STACK_UNDERFLOW_ERROR),
+        fails("1 upper", INVALID_TYPE_ERROR),
+        fails("'a' 1 ==", INVALID_TYPE_ERROR),
+        fails("'a' 1 +", INVALID_TYPE_ERROR),
+        // Lists
+        case(
+            "{ 1 2 3 } { } { 1 { \" a b \" 'c' } 2.5 }",
+            vec![
+                List(vec![Int(1), Int(2), Int(3)]),
+                List(vec![]),
+                List(vec![Int(1), List(vec![s("a b"), Char('c')]), Double(2.5)]),
+            ],
+            "",
+        ),
+        case("{ 1 { \" a b \" 'c' } } .", vec![], "{ 1 { \" a b \" 'c' } }"),
+        case(
+            ": t { 2 1 + dup } ; t\n{ 1\n2 }",
+            vec![List(vec![Int(3), Int(3)]), List(vec![Int(1), Int(2)])],
+            "",
+        ),
+        case(
+            "{ 10 20 30 } 1 index { 1 } 2 append { 1 2 3 4 } 1u 3l slice { 1 } { 2 3 } concat",
+            vec![
+                Int(20),
+                List(vec![Int(1), Int(2)]),
+                List(vec![Int(2), Int(3)]),
+                List(vec![Int(1), Int(2), Int(3)]),
+            ],
+            "",
+        ),
+        case(
+            "{ 1 2 3 } reverse { 3 1.5 2u } sort { \" b \" \" a \" } sort { 1 2 } length",
+            vec![
+                List(vec![Int(3), Int(2), Int(1)]),
+                List(vec![Double(1.5), UInt(2), Int(3)]),
+                List(vec![s("a"), s("b")]),
+                Int(2),
+            ],
+            "",
+        ),
+        case("{ 1 2\n3 }", vec![List(vec![Int(1), Int(2), Int(3)])], ""),
+        case("{ 1 2", vec![Int(1), Int(2)], "").status(EvalStatus::Pending),
+        // Lists compare element by element
+        case(
+            "{ 1 2 } { 1 2 } == { 1 2 } { 1 3 } > { 1 2 } { 1 } <",
+            vec![Bool(true), Bool(true), Bool(true)],
+            "",
+        ),
+        case(
+            "let a @a 1 push @a 2 push @a var>list { 5 6 } @a list>var @a len",
+            vec![List(vec![Int(1), Int(2)]), Int(2)],
+            "",
+        ),
+        fails("}", LIST_SYNTAX_ERROR),
+        fails("1 { drop drop }", STACK_UNDERFLOW_ERROR),
+        fails("{ 1 } 1 index", INDEX_OUT_OF_RANGE_ERROR),
+        fails("{ 1 } -1 index", INDEX_OUT_OF_RANGE_ERROR),
+        fails("{ 1 } 1.0 index", INVALID_TYPE_ERROR),
+        fails("1 0 index", INVALID_TYPE_ERROR),
+        fails("{ 1 2 } 1 0 slice", INDEX_OUT_OF_RANGE_ERROR),
+        fails("{ 1 2 } 0 3 slice", INDEX_OUT_OF_RANGE_ERROR),
+        fails("{ 1 \" a \" } sort", INVALID_TYPE_ERROR),
+        fails("{ 1 -1 sqrt 0 } sort", INVALID_TYPE_ERROR),
+        fails("{ map-new 1 1 map-put map-new } sort", INVALID_TYPE_ERROR),
+        fails("{ 1 } \" a \" concat", INVALID_TYPE_ERROR),
+        fails("index", STACK_UNDERFLOW_ERROR),
+        fails("{ } append", STACK_UNDERFLOW_ERROR),
+        fails("{ } 0 slice", STACK_UNDERFLOW_ERROR),
+        fails("reverse", STACK_UNDERFLOW_ERROR),
+        fails("sort", STACK_UNDERFLOW_ERROR),
+        fails("length", STACK_UNDERFLOW_ERROR),
+        fails("\" a \" length", INVALID_TYPE_ERROR),
+        fails("var>list", STACK_UNDERFLOW_ERROR),
+        fails("0 var>list", INVALID_TYPE_ERROR),
+        fails("{ } list>var", STACK_UNDERFLOW_ERROR),
+        fails("let a 1 @a list>var", INVALID_TYPE_ERROR),
+        // Maps
+        case(
+            "map-new map-new 1 \" one \" map-put \" two \" 2 map-put 1ul 'a' map-put",
+            vec![
+                map([]),
+                map([("two".into(), Int(2)), (1.into(), Char('a'))]),
+            ],
+            "",
+        ),
+        case(
+            "map-new 2 \" b \" map-put \" a \" 1 map-put -3 { 1 } map-put .",
+            vec![],
+            "map{ -3 => { 1 } 2 => \" b \" \" a \" => 1 }",
+        ),
+        case(
+            "map-new 1 10 map-put dup 1l 0 map-get swap dup 2 0 map-get swap dup 1u map-has? swap \" 1 \" map-has?",
+            vec![Int(10), Int(0), Bool(true), Bool(false)],
+            "",
+        ),
+        case(
+            "map-new 1 10 map-put 2 20 map-put 1 map-remove 3 map-remove",
+            vec![map([(2.into(), Int(20))])],
+            "",
+        ),
+        // Keys and values are in the same order, integers first
+        case(
+            "map-new \" a \" 1 map-put 5000000000l 2 map-put dup map-keys swap dup map-values swap length",
+            vec![
+                List(vec![Long(5_000_000_000), s("a")]),
+                List(vec![Int(2), Int(1)]),
+                Int(2),
+            ],
+            "",
+        ),
+        case(
+            ": sum ( map -- n ) 0 swap map-values dup length 0 for dup i index rot + swap next drop ;\nmap-new 1 10 map-put 2 20 map-put sum",
+            vec![Int(30)],
+            "",
+        ),
+        // map-each pushes the key and the value of each entry in key order
+        case(
+            ": flat map-each map-next ;\nmap-new 2 20 map-put \" a \" 1 map-put 1 10 map-put flat",
+            vec![Int(1), Int(10), Int(2), Int(20), s("a"), Int(1)],
+            "",
+        ),
+        case(
+            ": total ( map -- n ) 0 swap map-each swap drop + map-next ;\nmap-new 1 10 map-put 2 20 map-put total",
+            vec![Int(30)],
+            "",
+        ),
+        case(
+            ": flat map-each 99 map-next ;\n7 map-new flat",
+            vec![Int(7)],
+            "",
+        ),
+        case(
+            ": nest map-each swap drop map-each + map-next map-next ;\nmap-new 1 map-new 3 30 map-put 4 40 map-put map-put 2 map-new map-put nest",
+            vec![Int(33), Int(44)],
+            "",
+        ),
+        case(
+            "map-new 1 2 map-put map-new 1l 2.0 map-put == map-new 1 2 map-put map-new 1 3 map-put ==",
+            vec![Bool(true), Bool(false)],
+            "",
+        ),
+        case("map-new map-new <", vec![Bool(false)], ""),
+        fails("map-new 1.5 2 map-put", INVALID_TYPE_ERROR),
+        fails("map-new 'a' 0 map-get", INVALID_TYPE_ERROR),
+        fails("1 2 3 map-put", INVALID_TYPE_ERROR),
+        fails("map-new 1 map-put", STACK_UNDERFLOW_ERROR),
+        fails("map-new 1 map-get", STACK_UNDERFLOW_ERROR),
+        fails("map-new map-has?", STACK_UNDERFLOW_ERROR),
+        fails("1 1 map-has?", INVALID_TYPE_ERROR),
+        fails("map-new map-remove", STACK_UNDERFLOW_ERROR),
+        fails("{ } 1 map-remove", INVALID_TYPE_ERROR),
+        fails("map-keys", STACK_UNDERFLOW_ERROR),
+        fails("{ } map-values", INVALID_TYPE_ERROR),
+        fails(": t map-each map-next ; t", STACK_UNDERFLOW_ERROR),
+        fails(": t 1 map-each map-next ; t", INVALID_TYPE_ERROR),
+        fails(": t map-next ; t", LOOP_STACK_UNDERFLOW_ERROR),
+        fails(": t map-new 1 1 map-put map-each ; t", UNBALANCED_LOOP_ERROR),
+        fails(": t map-new map-each ; t", UNBALANCED_LOOP_ERROR),
+        // Structs
+        case(
+            "struct point x y end-struct 1 \" a \" point",
+            vec![point(Int(1), s("a"))],
+            "",
+        ),
+        case(
+            "struct point x y end-struct 1 2 point dup point.x swap point.y",
+            vec![Int(1), Int(2)],
+            "",
+        ),
+        case(
+            "struct point x y end-struct 1 2 point 5 point.x! 'c' point.y!",
+            vec![point(Int(5), Char('c'))],
+            "",
+        ),
+        case(
+            "struct point x y end-struct 1 2 point point? { } point? 1 point?",
+            vec![Bool(true), Bool(false), Bool(false)],
+            "",
+        ),
+        case(
+            "struct point x y end-struct 1 { \" a \" } point . 1 2 point to_str",
+            vec![s("point{ x: 1 y: 2 }")],
+            "point{ x: 1 y: { \" a \" } }",
+        ),
+        case(
+            "struct empty end-struct empty dup empty? swap empty ==",
+            vec![Bool(true), Bool(true)],
+            "",
+        ),
+        case(
+            "struct point x y end-struct : origin ( -- p ) 0 0 point ;\n: right ( p -- p ) dup point.x 1 + point.x! ;\norigin right right point.x",
+            vec![Int(2)],
+            "",
+        ),
+        // Values of an earlier declaration aren't instances of the new one
+        case(
+            "struct point x y end-struct 1 2 point struct point x y z end-struct point? 1 2 3 point point?",
+            vec![Bool(false), Bool(true)],
+            "",
+        ),
+        fails("struct", STRUCT_SYNTAX_ERROR),
+        fails("struct point x y", STRUCT_SYNTAX_ERROR),
+        fails("struct end-struct", STRUCT_SYNTAX_ERROR),
+        fails("struct point x x end-struct", STRUCT_SYNTAX_ERROR),
+        fails("struct point.x end-struct", STRUCT_SYNTAX_ERROR),
+        // The generated words can't be shadowed by other words
+        fails("struct dup x end-struct", STRUCT_SYNTAX_ERROR),
+        fails("struct map-has x end-struct", STRUCT_SYNTAX_ERROR),
+        fails(": p 1 ; struct p x end-struct", STRUCT_SYNTAX_ERROR),
+        fails("struct 12 x end-struct", STRUCT_SYNTAX_ERROR),
+        fails("struct point x! end-struct", STRUCT_SYNTAX_ERROR),
+        fails("struct point x? end-struct", STRUCT_SYNTAX_ERROR),
+        fails("struct p? x end-struct struct p x end-struct", STRUCT_SYNTAX_ERROR),
+        fails("struct point x y end-struct 1 point", STACK_UNDERFLOW_ERROR),
+        fails("struct point x y end-struct point.x", STACK_UNDERFLOW_ERROR),
+        fails("struct point x y end-struct 1 point.x", INVALID_TYPE_ERROR),
+        fails(
+            "struct point x y end-struct struct size x y end-struct 1 2 size point.x",
+            INVALID_TYPE_ERROR,
+        ),
+        fails("struct point x y end-struct 1 2 point point.x!", STACK_UNDERFLOW_ERROR),
+        fails("struct point x y end-struct 1 2 point.x!", INVALID_TYPE_ERROR),
+        fails("struct point x y end-struct point?", STACK_UNDERFLOW_ERROR),
+        fails("struct point x y end-struct point.z", UNKNOWN_WORD_ERROR),
+        // Diagnostics
+        case("trace 1 notrace", vec![Int(1)], ""),
+        case("profile 1 noprofile", vec![Int(1)], ""),
+        // Testing
+        case(
+            "T{ 1 2 + -> 3 }T -1 assert true assert 1 1 assert-eq T{ 2 1 < -> true }T",
+            vec![],
+            "",
+        ),
+        fails("T{ 1 -> 2 }T", TEST_FAILED_ERROR),
+        fails("0 assert", TEST_FAILED_ERROR),
+        fails("false assert", TEST_FAILED_ERROR),
+        fails("1.5 assert", NOT_A_FLAG_ERROR),
+        fails("1 2 assert-eq", TEST_FAILED_ERROR),
+        fails("->", TEST_SYNTAX_ERROR),
+        fails("T{ }T", TEST_SYNTAX_ERROR),
+        fails("assert", STACK_UNDERFLOW_ERROR),
+        fails("1 assert-eq", STACK_UNDERFLOW_ERROR),
+    ];
+    #[cfg(feature = "bigint")]
+    let cases = cases.into_iter().chain(bigint_cases()).collect();
+    cases
+}
+
+#[cfg(feature = "bigint")]
+fn bigint_cases() -> Vec<Case> {
+    let big = |val: &str| Types::BigInt(val.parse().unwrap());
+
+    vec![
+        case(
+            "123456789012345678901234n -5n",
+            vec![big("123456789012345678901234"), big("-5")],
+            "",
+        ),
+        case(
+            "18446744073709551616n 1 + 2l 3n * 7n 2u / 7n 2 % 1n 3n -",
+            vec![big("18446744073709551617"), big("6"), big("3"), big("1"), big("-2")],
+            "",
+        ),
+        // Once promoted the result stays a BigInt
+        case(
+            "9223372036854775807l 2 * 2 /",
+            vec![big("9223372036854775807")],
+            "",
+        ),
+        case(
+            "1n 2 < 1n 1.5 + 1n 1i128 == 2n 1.0f >",
+            vec![Bool(false), Double(2.5), Bool(true), Bool(false)],
+            "",
+        ),
+        case(
+            "-5n abs 5n negate 1n 2n max 9n sqrt 7n floor",
+            vec![big("5"), big("-5"), big("2"), Double(3.0), big("7")],
+            "",
+        ),
+        case(
+            "5n to_int 5n to_byte 5n to_double 5n to_str 2.9 to_bigint \" 12 \" to_bigint 7u to_bigint true to_bigint",
+            vec![
+                Int(5),
+                Byte(5),
+                Double(5.0),
+                s("5"),
+                big("2"),
+                big("12"),
+                big("7"),
+                big("-1"),
+            ],
+            "",
+        ),
+        fails("300n to_byte", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("1.0 0 / to_bigint", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("\" x \" to_bigint", TYPE_CONVERSION_FAILURE_ERROR),
+        fails("to_bigint", STACK_UNDERFLOW_ERROR),
+        fails("1n 0 /", DIVISION_BY_ZERO_ERROR),
+        fails("1n 1 band", INVALID_TYPE_ERROR),
+        case("5n to_decimal 1n 1.5d +", vec![dec("5"), Double(2.5)], ""),
+        fails("1000000000000000000000000000000000000000n to_decimal", TYPE_CONVERSION_FAILURE_ERROR),
+    ]
+}
+
+fn new_engine(names: &Rc<RefCell<BTreeSet<String>>>) -> Engine {
+    let mut engine = Engine::new();
+    engine.import_word_list(Standard::new());
+
+    let names = names.clone();
+    engine.tracing = true;
+    engine.set_trace_hook(move |event| {
+        names.borrow_mut().insert(event.name.to_string());
+    });
+
+    engine
+}
+
+fn run(case: &Case, names: &Rc<RefCell<BTreeSet<String>>>) {
+    let mut engine = new_engine(names);
+
+    let mut output = String::new();
+    let mut result = Ok(());
+    for line in case.program.split('\n') {
+        match engine.eval(line.to_string()) {
+            Ok(outcome) => output += &outcome.output,
+            Err(err) => {
+                result = Err(err);
+                break;
+            }
+        }
+    }
+
+    match (case.error, result) {
+        (None, Ok(())) => {
+            assert_eq!(engine.main_stack, case.stack, "stack of {:?}", case.program);
+            assert_eq!(output, case.output, "output of {:?}", case.program);
+            assert_eq!(engine.status(), case.status, "status of {:?}", case.program);
+        }
+        (Some(error), Err(err)) => {
+            assert!(
+                err.starts_with(error),
+                "{:?} failed with {:?} instead of {:?}",
+                case.program,
+                err,
+                error
+            );
+        }
+        (None, Err(err)) => panic!("{:?} failed with {:?}", case.program, err),
+        (Some(error), Ok(())) => panic!("{:?} didn't fail with {:?}", case.program, error),
+    }
+}
+
+#[test]
+fn standard_words() {
+    let names = Rc::new(RefCell::new(BTreeSet::new()));
+
+    for case in cases().iter() {
+        run(case, &names);
+    }
+}
+
+#[test]
+fn every_word_is_covered() {
+    let names = Rc::new(RefCell::new(BTreeSet::new()));
+
+    for case in cases().iter() {
+        run(case, &names);
+    }
+    // The report is skipped in `cases` as its output depends on timing
+    let mut engine = new_engine(&names);
+    engine.eval("profile-report".to_string()).unwrap();
+
+    let missing: Vec<&str> = Standard::new()
+        .get_words()
+        .iter()
+        .map(|word| word.0)
+        .filter(|name| !names.borrow().contains(*name))
+        .collect();
+    assert!(missing.is_empty(), "words without a case: {:?}", missing);
+}
+
+// Result types of the binary math words, rows are the type of the value
+// below the top of the stack, columns the type of the top value
+const NUMBER_LITERALS: [&str; 9] = ["0x06", "6", "6u", "6l", "6ul", "6i128", "6d", "6f", "6.0"];
+#[rustfmt::skip]
+const MATH_PROMOTION: [[&str; 9]; 9] = [
+    // Byte      Int        UInt       Long       ULong      Int128     Decimal    Float     Double
+    ["byte",    "int",     "uint",    "long",    "ulong",   "int128",  "decimal", "float",  "double"], // Byte
+    ["int",     "int",     "long",    "long",    "int128",  "int128",  "decimal", "double", "double"], // Int
+    ["uint",    "long",    "uint",    "long",    "ulong",   "int128",  "decimal", "double", "double"], // UInt
+    ["long",    "long",    "long",    "long",    "int128",  "int128",  "decimal", "double", "double"], // Long
+    ["ulong",   "int128",  "ulong",   "int128",  "ulong",   "int128",  "decimal", "double", "double"], // ULong
+    ["int128",  "int128",  "int128",  "int128",  "int128",  "int128",  "double",  "double", "double"], // Int128
+    ["decimal", "decimal", "decimal", "decimal", "decimal", "double",  "decimal", "double", "double"], // Decimal
+    ["float",   "double",  "double",  "double",  "double",  "double",  "double",  "float",  "double"], // Float
+    ["double",  "double",  "double",  "double",  "double",  "double",  "double",  "double", "double"], // Double
+];
+
+fn number(kind: &str, val: f64) -> Types {
+    match kind {
+        "byte" => Byte(val as u8),
+        "int" => Int(val as i32),
+        "uint" => UInt(val as u32),
+        "long" => Long(val as i64),
+        "ulong" => ULong(val as u64),
+        "int128" => Int128(val as i128),
+        "decimal" => Types::Decimal((val as i128).into()),
+        "float" => Float(val as f32),
+        "double" => Double(val),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn math_promotion() {
+    // 6 op 3 for every pair of number types
+    let words = [("+", 9.0), ("-", 3.0), ("*", 18.0), ("/", 2.0), ("%", 0.0)];
+
+    for (row, a) in NUMBER_LITERALS.iter().enumerate() {
+        for (column, b) in NUMBER_LITERALS.iter().enumerate() {
+            let b = b.replace('6', "3");
+            for (word, result) in words.iter() {
+                let program = format!("{} {} {}", a, b, word);
+
+                let mut engine = Engine::new();
+                engine.import_word_list(Standard::new());
+                let outcome = engine.eval(program.clone()).unwrap();
+
+                assert_eq!(
+                    outcome.stack,
+                    vec![number(MATH_PROMOTION[row][column], *result)],
+                    "{}",
+                    program
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn comparison_matrix() {
+    // Comparisons work across all number types and always return a Bool
+    let words = [
+        ("==", [false, true, false]),
+        ("!=", [true, false, true]),
+        (">", [false, false, true]),
+        ("<", [true, false, false]),
+    ];
+
+    for a in NUMBER_LITERALS.iter() {
+        for b in NUMBER_LITERALS.iter() {
+            for (word, results) in words.iter() {
+                // The top value is smaller than, equal to and greater than
+                // the one below it
+                for (top, result) in ["5", "6", "7"].iter().zip(results.iter()) {
+                    let program = format!("{} {} {}", a, b.replace('6', top), word);
+
+                    let mut engine = Engine::new();
+                    engine.import_word_list(Standard::new());
+                    let outcome = engine.eval(program.clone()).unwrap();
+
+                    assert_eq!(outcome.stack, vec![Bool(*result)], "{}", program);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,277 @@
+// Fuzzing harness
+//
+// Shared by the `eval` target in fuzz/ and the smoke test below. Input bytes
+// are mapped to tokens instead of being evaluated as text, so the fuzzer
+// spends its time on programs that reach the words rather than on unknown
+// word errors. Every program runs in a sandboxed engine, the only thing that
+// counts as a failure is a panic.
+
+use super::{Engine, EngineLimits, Standard, Types, WordList, MAX_IMAGE_DEPTH};
+
+pub const TOKENS: &[&str] = &[
+    // Values
+    "0",
+    "1",
+    "-1",
+    "2",
+    "7",
+    "255",
+    "2147483647",
+    "-2147483648",
+    "9223372036854775807l",
+    "-9223372036854775808l",
+    "1l",
+    "4294967295u",
+    "18446744073709551615ul",
+    "-170141183460469231731687303715884105728i128",
+    "123456789012345678901234567890n",
+    "0.1d",
+    "'a'",
+    "'\\u{1F600}'",
+    "'\\u{d800}'",
+    "-12.50d",
+    "0x00",
+    "0xff",
+    "0xzz",
+    "1.5",
+    "0.0",
+    "1e308",
+    "2.5f",
+    "\"",
+    "hello",
+    // Words
+    "(",
+    ")",
+    "if",
+    "else",
+    "then",
+    "for",
+    "next",
+    "bynext",
+    "while",
+    "do",
+    "again",
+    "i",
+    "let",
+    "x",
+    "@x",
+    "@y",
+    "push",
+    "pop",
+    "get",
+    "set",
+    "len",
+    "+",
+    "-",
+    "*",
+    "/",
+    "%",
+    "abs",
+    "negate",
+    "min",
+    "sqrt",
+    "pow",
+    "round",
+    "checked",
+    "wrapping",
+    "saturating",
+    "==",
+    "!=",
+    "and",
+    "or",
+    "not",
+    "true",
+    "false",
+    ">",
+    "<",
+    "band",
+    "bxor",
+    "binvert",
+    "lshift",
+    "rshift",
+    "arshift",
+    "popcount",
+    "rotl",
+    "dup",
+    "2dup",
+    "drop",
+    "swap",
+    "rot",
+    "peek",
+    ".",
+    ":",
+    ";",
+    "w",
+    "--",
+    "emit",
+    "nl",
+    "silent",
+    "input",
+    "to_int",
+    "to_long",
+    "to_float",
+    "to_double",
+    "to_byte",
+    "to_uint",
+    "to_ulong",
+    "to_int128",
+    "to_bigint",
+    "to_decimal",
+    "rescale",
+    "to_str",
+    "concat",
+    "split",
+    "wsplit",
+    "chars",
+    "char>int",
+    "int>char",
+    "alpha?",
+    "digit?",
+    "space?",
+    "upper",
+    "lower",
+    "{",
+    "}",
+    "index",
+    "append",
+    "slice",
+    "reverse",
+    "sort",
+    "length",
+    "var>list",
+    "list>var",
+    "map-new",
+    "map-put",
+    "map-get",
+    "map-has?",
+    "map-remove",
+    "map-keys",
+    "map-values",
+    "map-each",
+    "map-next",
+    "struct",
+    "end-struct",
+    "x.x",
+    "x.x!",
+    "x?",
+    "trace",
+    "notrace",
+    "profile",
+    "noprofile",
+    "profile-report",
+    "T{",
+    "->",
+    "}T",
+    "assert",
+    "assert-eq",
+    "bye",
+];
+
+/// Turns fuzzer input into lines of tokens, one byte per token. Bytes past
+/// the end of the token table end the current line.
+pub fn decode(data: &[u8]) -> Vec<String> {
+    let mut lines = vec![String::new()];
+
+    for byte in data {
+        match TOKENS.get(*byte as usize) {
+            Some(token) => {
+                let line = lines.last_mut().unwrap();
+                line.push_str(token);
+                line.push(' ');
+            }
+            None => lines.push(String::new()),
+        }
+    }
+
+    lines
+}
+
+pub fn sandboxed_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.import_word_list(Standard::new());
+    engine.limits = EngineLimits {
+        max_executed_words: Some(10_000),
+        max_stack_depth: Some(1000),
+        max_loop_depth: Some(64),
+        max_call_depth: Some(64),
+        max_memory: Some(1 << 20),
+        max_output: Some(1 << 16),
+    };
+    engine
+}
+
+/// Evaluates the program encoded in `data`, panics if the engine does.
+pub fn run(data: &[u8]) {
+    let mut engine = sandboxed_engine();
+
+    for line in decode(data) {
+        if !engine.running {
+            break;
+        }
+        // Errors are fine, panics are not
+        let _ = engine.eval(line);
+    }
+
+    // The memory tracked by the stacks has to add up to the values left on them
+    let size = |values: &[Types]| values.iter().map(|v| v.size()).sum::<usize>();
+    let variables: usize = engine
+        .variable_stack
+        .iter()
+        .map(|(name, values)| name.len() + size(values))
+        .sum();
+    assert_eq!(engine.memory_usage(), size(&engine.main_stack) + variables);
+
+    // Whatever state the program left behind has to survive an image round
+    // trip, unless it is nested too deep to be loaded
+    let mut values = engine.main_stack.iter().chain(
+        engine
+            .variable_stack
+            .iter()
+            .flat_map(|(_, values)| values.iter()),
+    );
+    if values.any(|val| nesting(val) >= MAX_IMAGE_DEPTH) {
+        return;
+    }
+    let image = engine.save_image();
+    let mut restored = sandboxed_engine();
+    restored.load_image(image.as_str()).unwrap();
+    assert_eq!(restored.save_image(), image);
+}
+
+// Lists, maps and structs around the innermost value of `val`
+fn nesting(val: &Types) -> usize {
+    match val {
+        Types::List(values) => 1 + values.iter().map(nesting).max().unwrap_or(0),
+        Types::Map(map) => 1 + map.values().map(nesting).max().unwrap_or(0),
+        Types::Struct(val) => {
+            1 + val
+                .fields
+                .iter()
+                .map(|(_, v)| nesting(v))
+                .max()
+                .unwrap_or(0)
+        }
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn random_programs() {
+    // xorshift64, the smoke test has to be reproducible
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    for _ in 0..5000 {
+        let len = (next() % 64) as usize;
+        let data: Vec<u8> = (0..len)
+            .map(|_| (next() % (TOKENS.len() as u64 + 4)) as u8)
+            .collect();
+        run(&data);
+    }
+}
@@ -0,0 +1,803 @@
+mod conformance;
+mod fuzz;
+mod properties;
+
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+use crate::prelude::{
+    Arithmetic, Control, CoreStack, Decimal, Engine, EngineLimits, EvalStatus, MapKey, Math,
+    Standard, TracePhase, Types, VarRef, WordList, DIVISION_BY_ZERO_ERROR, INVALID_IMAGE_ERROR,
+    LIMIT_EXCEEDED_ERROR, MAX_IMAGE_DEPTH, NOT_A_FLAG_ERROR, OVERFLOW_ERROR, STACK_EFFECT_ERROR,
+    TEST_FAILED_ERROR, UNKNOWN_WORD_ERROR, VARIABLE_NOT_DEFINED,
+};
+
+#[test]
+fn math_ops() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    let line = "2 3 + . 2 3 * . 4 2 / . 1 1 - .".to_string();
+
+    let outcome = engine.eval(line).unwrap();
+
+    assert_eq!(outcome.output.as_str(), "5 6 2 0");
+    assert_eq!(outcome.status, EvalStatus::Completed);
+}
+
+#[test]
+fn logic_ops() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    let line = "2 3 > . 2 3 < . 4 2 != . 1 1 == .".to_string();
+
+    let outcome = engine.eval(line).unwrap();
+
+    assert_eq!(outcome.output.as_str(), "true false true true");
+    assert_eq!(outcome.status, EvalStatus::Completed);
+}
+
+#[test]
+fn stack_ops() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    let line = "2 dup . . 2 3 2dup . . . . 4 2 swap . . 1 2 3 rot . . . 7 peek .".to_string();
+
+    let outcome = engine.eval(line).unwrap();
+
+    assert_eq!(outcome.output.as_str(), "2 2 3 2 3 2 4 2 1 3 2 7 7");
+    assert_eq!(outcome.status, EvalStatus::Completed);
+}
+
+#[test]
+fn custom_words() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    let line = ": sq dup * ; 2 sq . 2 sq sq .".to_string();
+
+    let outcome = engine.eval(line).unwrap();
+
+    assert_eq!(outcome.output.as_str(), "4 16");
+    assert_eq!(outcome.status, EvalStatus::Completed);
+}
+
+#[test]
+fn variables() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    let line = "let a @a 5 push @a pop . @a dup 6 push 9 push @a dup pop swap pop . .".to_string();
+
+    let outcome = engine.eval(line).unwrap();
+
+    assert_eq!(outcome.output.as_str(), "5 6 9");
+    assert_eq!(outcome.status, EvalStatus::Completed);
+
+    // Handles from another engine only work if the variable is there too
+    engine.main_stack.push(Types::VarRef(VarRef {
+        index: 0,
+        name: "b".to_string(),
+    }));
+    let err = engine.eval("len".to_string()).unwrap_err();
+    assert!(err.starts_with(VARIABLE_NOT_DEFINED));
+}
+
+#[test]
+fn strings() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    let line = "\" hello \" \" world \" 2dup + peek wsplit . . concat .".to_string();
+
+    let outcome = engine.eval(line).unwrap();
+
+    assert_eq!(
+        outcome.output.as_str(),
+        "hello world world hello helloworld"
+    );
+    assert_eq!(outcome.status, EvalStatus::Completed);
+}
+
+#[test]
+fn maps() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    let mut map = BTreeMap::new();
+    map.insert(MapKey::from("a"), Types::Int(1));
+    map.insert(MapKey::from(2), Types::Str("two".to_string()));
+    engine.main_stack.push(Types::Map(map));
+
+    let line = "dup \" a \" 0 map-get . 2l \" b \" map-put".to_string();
+
+    let outcome = engine.eval(line).unwrap();
+
+    assert_eq!(outcome.output.as_str(), "1");
+    match engine.main_stack.pop() {
+        Some(Types::Map(map)) => {
+            assert_eq!(map.get(&MapKey::from("a")), Some(&Types::Int(1)));
+            assert_eq!(
+                map.get(&MapKey::from(2)),
+                Some(&Types::Str("b".to_string()))
+            );
+        }
+        other => panic!("expected a map, got {:?}", other),
+    }
+}
+
+#[test]
+fn type_conversion() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    let line = "17 to_long peek to_float peek to_double peek to_byte peek to_str .".to_string();
+
+    let outcome = engine.eval(line).unwrap();
+
+    assert_eq!(outcome.output.as_str(), "17 17 17 17 17");
+    assert_eq!(outcome.status, EvalStatus::Completed);
+}
+
+#[test]
+fn input() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    let line1 = "\" abc \" input".to_string();
+    let line2 = "cba".to_string();
+    let line3 = ".".to_string();
+
+    let outcome = engine.eval(line1).unwrap();
+
+    assert_eq!(outcome.output.as_str(), "abc");
+    assert_eq!(outcome.status, EvalStatus::AwaitingInput);
+
+    let outcome = engine.eval(line2).unwrap();
+
+    assert_eq!(outcome.output.as_str(), "");
+    assert_eq!(outcome.status, EvalStatus::Completed);
+    assert_eq!(outcome.stack, vec![Types::Str("cba".to_string())]);
+
+    let outcome = engine.eval(line3).unwrap();
+
+    assert_eq!(outcome.output.as_str(), "cba");
+    assert_eq!(outcome.status, EvalStatus::Completed);
+}
+
+#[test]
+fn silent() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    let line1 = "17".to_string();
+    let line2 = "-1 silent".to_string();
+    let line3 = ".".to_string();
+    let line4 = "0 silent".to_string();
+
+    let outcome = engine.eval(line1).unwrap();
+
+    assert_eq!(outcome.output.as_str(), "");
+    assert_eq!(outcome.stack, vec![Types::Int(17)]);
+    assert!(!engine.silent);
+
+    engine.eval(line2).unwrap();
+    assert!(engine.silent);
+
+    let outcome = engine.eval(line3).unwrap();
+
+    assert_eq!(outcome.output.as_str(), "17");
+    assert!(outcome.stack.is_empty());
+
+    engine.eval(line4).unwrap();
+    assert!(!engine.silent);
+}
+
+#[test]
+fn eval_status() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    let outcome = engine.eval(": sq dup *".to_string()).unwrap();
+    assert_eq!(outcome.status, EvalStatus::Compiling);
+
+    let outcome = engine.eval("; 3 sq".to_string()).unwrap();
+    assert_eq!(outcome.status, EvalStatus::Completed);
+    assert_eq!(outcome.stack, vec![Types::Int(9)]);
+
+    let outcome = engine.eval("( a comment".to_string()).unwrap();
+    assert_eq!(outcome.status, EvalStatus::Pending);
+
+    let outcome = engine.eval(") \" a string".to_string()).unwrap();
+    assert_eq!(outcome.status, EvalStatus::Pending);
+
+    let outcome = engine.eval("\"".to_string()).unwrap();
+    assert_eq!(outcome.status, EvalStatus::Completed);
+
+    let outcome = engine.eval("{ 1 { 2".to_string()).unwrap();
+    assert_eq!(outcome.status, EvalStatus::Pending);
+
+    let outcome = engine.eval("} 3".to_string()).unwrap();
+    assert_eq!(outcome.status, EvalStatus::Pending);
+
+    let outcome = engine.eval("}".to_string()).unwrap();
+    assert_eq!(outcome.status, EvalStatus::Completed);
+
+    let outcome = engine.eval("bye".to_string()).unwrap();
+    assert_eq!(outcome.status, EvalStatus::Exited);
+}
+
+#[test]
+fn image() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    let line =
+        ": sq dup * ; let a @a \" hello  there \" push @a 1.5f push 3u 4ul 5i128 -12.50d true 'é' struct p a end-struct 1 p @a { 1 { \" a \" } } map-new \" k \" 2 map-put -3 { 1 } map-put 7 2l saturating"
+            .to_string();
+
+    engine.eval(line).unwrap();
+
+    let image = engine.save_image();
+
+    let mut restored = Engine::new();
+
+    restored.import_word_list(Standard::new());
+
+    restored.load_image(image.as_str()).unwrap();
+
+    assert_eq!(restored.save_image(), image);
+    assert_eq!(restored.arithmetic, Arithmetic::Saturating);
+
+    let outcome = restored
+        .eval("sq . . . . . . . . . . . . 2 p p.a . @a pop . @a pop .".to_string())
+        .unwrap();
+
+    assert_eq!(
+        outcome.output.as_str(),
+        "4 7 map{ -3 => { 1 } \" k \" => 2 } { 1 { \" a \" } } @a p{ a: 1 } é true -12.50 5 4 3 2 1.5 hello there"
+    );
+}
+
+#[test]
+fn image_rollback() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    engine.eval("1 2 let a".to_string()).unwrap();
+
+    let checkpoint = engine.save_image();
+
+    engine.eval("+ let b : x 5 ;".to_string()).unwrap();
+    engine.load_image(checkpoint.as_str()).unwrap();
+
+    assert_eq!(engine.main_stack, vec![Types::Int(1), Types::Int(2)]);
+    assert_eq!(engine.variable_stack.len(), 1);
+    assert!(engine.compiled_words.is_empty());
+
+    assert!(engine.load_image("sorth-image 999").is_err());
+    assert!(engine.load_image("garbage").is_err());
+
+    // Compile mode without a definition in progress and the other way around
+    let compiling = checkpoint.replace(" normal ", " compile ");
+    assert!(engine.load_image(compiling.as_str()).is_err());
+    let defining = checkpoint.replace("compiling 0", "compiling 1 1:x 1:a 0 0 1:a 0 0 0");
+    assert!(engine.load_image(defining.as_str()).is_err());
+
+    // Nested values have a depth limit instead of overflowing the stack
+    let nested = |depth: usize| {
+        checkpoint.replace(
+            "stack 2",
+            &format!("stack 3 {}i 1", "list 1 ".repeat(depth - 1)),
+        )
+    };
+    engine.load_image(nested(MAX_IMAGE_DEPTH).as_str()).unwrap();
+    assert_eq!(engine.main_stack.len(), 3);
+    assert_eq!(
+        engine.load_image(nested(MAX_IMAGE_DEPTH + 1).as_str()),
+        Err(INVALID_IMAGE_ERROR.to_string())
+    );
+    assert!(engine.load_image(nested(1_000_000).as_str()).is_err());
+
+    engine.load_image(checkpoint.as_str()).unwrap();
+    assert_eq!(engine.main_stack, vec![Types::Int(1), Types::Int(2)]);
+}
+
+#[test]
+fn limits() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    engine.limits = EngineLimits {
+        max_executed_words: Some(1000),
+        max_stack_depth: Some(16),
+        max_call_depth: Some(8),
+        max_memory: Some(4096),
+        max_output: Some(64),
+        ..Default::default()
+    };
+
+    let err = engine
+        .eval(": forever while -1 do again ; forever".to_string())
+        .unwrap_err();
+    assert_eq!(
+        err.lines().next().unwrap(),
+        LIMIT_EXCEEDED_ERROR.to_string() + "executed words"
+    );
+
+    assert!(engine.eval("1".to_string()).is_err());
+    engine.reset_usage();
+    assert!(engine.eval("1".to_string()).is_ok());
+
+    engine.reset();
+    let err = engine
+        .eval(": deep 0 17 0 for dup next ; deep".to_string())
+        .unwrap_err();
+    assert_eq!(
+        err.lines().next().unwrap(),
+        LIMIT_EXCEEDED_ERROR.to_string() + "stack depth"
+    );
+
+    engine.reset();
+    let err = engine.eval(": down down ; down".to_string()).unwrap_err();
+    assert_eq!(
+        err.lines().next().unwrap(),
+        LIMIT_EXCEEDED_ERROR.to_string() + "call depth"
+    );
+
+    engine.reset();
+    let err = engine
+        .eval(": loud 100 0 for \" abcdefgh \" . next ; loud".to_string())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        LIMIT_EXCEEDED_ERROR.to_string()
+            + "output size\n"
+            + "  at <input>:6:31 in loud\n"
+            + "  at <input>:6:40\n"
+    );
+
+    engine.reset();
+    let err = engine
+        .eval(
+            ": big let a 100 0 for @a \" abcdefghijklmnopqrstuvwxyz \" push next ; big".to_string(),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.lines().next().unwrap(),
+        LIMIT_EXCEEDED_ERROR.to_string() + "memory"
+    );
+
+    assert!(engine.eval("2 3 + .".to_string()).is_err());
+    let outcome = engine.eval("let a 2 3 + .".to_string()).unwrap();
+    assert_eq!(outcome.output.as_str(), "5");
+}
+
+#[test]
+fn restricted_word_lists() {
+    let mut engine = Engine::new();
+
+    engine.import_word_list(Control::new());
+    engine.import_word_list(Math::new());
+    engine.import_word_list(CoreStack::new());
+
+    let outcome = engine.eval("2 3 + .".to_string()).unwrap();
+    assert_eq!(outcome.output.as_str(), "5");
+
+    let err = engine.eval("\" hi \" input".to_string()).unwrap_err();
+    assert_eq!(
+        err.lines().next().unwrap(),
+        UNKNOWN_WORD_ERROR.to_string() + "\""
+    );
+
+    engine.exclude_words(&["*"]);
+    let err = engine.eval("2 3 *".to_string()).unwrap_err();
+    assert_eq!(
+        err.lines().next().unwrap(),
+        UNKNOWN_WORD_ERROR.to_string() + "*"
+    );
+
+    assert!(engine.override_word("+", |s| {
+        s.main_stack.pop();
+        Ok("".to_string())
+    }));
+    assert!(!engine.override_word("input", |_| Ok("".to_string())));
+    engine.main_stack.clear();
+    let outcome = engine.eval("2 3 +".to_string()).unwrap();
+    assert_eq!(outcome.stack, vec![Types::Int(2)]);
+
+    engine.retain_words(&["<int>", "."]);
+    assert_eq!(engine.words.len(), 2);
+    assert!(engine.eval("dup".to_string()).is_err());
+}
+
+#[test]
+fn custom_word_names() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    let line = ": dupx 1 ; : f 2 ; : fib 3 ; 5 dup f fib dupx".to_string();
+
+    let outcome = engine.eval(line).unwrap();
+
+    assert_eq!(
+        outcome.stack,
+        vec![
+            Types::Int(5),
+            Types::Int(5),
+            Types::Int(2),
+            Types::Int(3),
+            Types::Int(1)
+        ]
+    );
+
+    engine.eval(": f 4 ;".to_string()).unwrap();
+    assert_eq!(engine.compiled_words.len(), 3);
+
+    // Standard words can't be shadowed
+    assert!(engine.eval(": e 2 ;".to_string()).is_err());
+    assert_eq!(engine.status(), EvalStatus::Completed);
+    engine.main_stack.clear();
+    let outcome = engine.eval("e".to_string()).unwrap();
+    assert_eq!(outcome.stack, vec![Types::Double(core::f64::consts::E)]);
+    assert_eq!(engine.compiled_words.len(), 3);
+}
+
+#[test]
+fn stepping() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    engine.eval(": sq dup * ;".to_string()).unwrap();
+
+    engine.load("3 sq .".to_string()).unwrap();
+    assert_eq!(engine.next_token(), Some("3".to_string()));
+
+    let step = engine.step().unwrap().unwrap();
+    assert_eq!(step.token.as_str(), "3");
+    assert_eq!((step.depth, step.word_index), (0, 0));
+    assert_eq!(step.main_stack, vec![Types::Int(3)]);
+
+    let step = engine.step().unwrap().unwrap();
+    assert_eq!(step.token.as_str(), "sq");
+
+    let step = engine.step().unwrap().unwrap();
+    assert_eq!(step.token.as_str(), "dup");
+    assert_eq!((step.depth, step.word_index), (1, 0));
+    assert_eq!(step.main_stack, vec![Types::Int(3), Types::Int(3)]);
+
+    engine.step().unwrap().unwrap();
+
+    let step = engine.step().unwrap().unwrap();
+    assert_eq!(step.token.as_str(), ".");
+    assert_eq!(step.output.as_str(), "9");
+
+    assert!(engine.is_idle());
+    assert!(engine.step().unwrap().is_none());
+}
+
+#[test]
+fn breakpoints() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    engine
+        .eval(": sq dup * ; : quad sq sq ;".to_string())
+        .unwrap();
+    engine.breakpoints.push("*".to_string());
+
+    engine.load("2 quad .".to_string()).unwrap();
+
+    assert_eq!(engine.resume().unwrap().as_str(), "");
+    assert!(engine.at_breakpoint());
+    assert_eq!(engine.main_stack, vec![Types::Int(2), Types::Int(2)]);
+
+    engine.resume().unwrap();
+    assert!(engine.at_breakpoint());
+    assert_eq!(engine.main_stack, vec![Types::Int(4), Types::Int(4)]);
+
+    assert_eq!(engine.resume().unwrap().as_str(), "16");
+    assert!(engine.is_idle());
+
+    engine.load("1 foo 2".to_string()).unwrap();
+    engine.step().unwrap();
+    assert!(engine.step().is_err());
+    assert!(engine.is_idle());
+}
+
+#[test]
+fn tracing() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let hook_events = events.clone();
+    engine.set_trace_hook(move |e| {
+        if e.phase == TracePhase::After {
+            hook_events
+                .borrow_mut()
+                .push((e.token.to_string(), e.depth, e.stack.len()));
+        }
+    });
+
+    engine.eval(": sq dup * ;".to_string()).unwrap();
+    engine.eval("trace 3 sq notrace 4".to_string()).unwrap();
+
+    assert_eq!(
+        *events.borrow(),
+        vec![
+            ("3".to_string(), 0, 1),
+            ("sq".to_string(), 0, 1),
+            ("dup".to_string(), 1, 2),
+            ("*".to_string(), 1, 1),
+            ("notrace".to_string(), 0, 1),
+        ]
+    );
+    assert!(!engine.tracing);
+}
+
+#[test]
+fn profiling() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    engine.eval(": sq dup * ;".to_string()).unwrap();
+    engine.eval(": quad sq sq ;".to_string()).unwrap();
+    engine
+        .eval("profile 2 quad 3 quad . . noprofile".to_string())
+        .unwrap();
+
+    let quad = &engine.profile["quad"];
+    assert_eq!(quad.calls, 2);
+    assert_eq!(quad.inclusive_words, 14);
+    assert_eq!(quad.exclusive_words, 4);
+    assert!(quad.exclusive_time <= quad.inclusive_time);
+
+    let sq = &engine.profile["sq"];
+    assert_eq!(sq.calls, 4);
+    assert_eq!(sq.inclusive_words, 12);
+    assert_eq!(sq.exclusive_words, 8);
+
+    assert_eq!(engine.profile["dup"].calls, 4);
+    assert_eq!(engine.profile["<int>"].calls, 2);
+    assert_eq!(engine.profile["."].calls, 2);
+    assert_eq!(engine.profile["noprofile"].calls, 1);
+
+    let outcome = engine.eval("profile-report".to_string()).unwrap();
+    assert!(outcome.output.starts_with("\nword"));
+    assert!(outcome.output.contains("\nquad "));
+
+    engine.reset_profile();
+    assert!(engine.profile.is_empty());
+
+    // Calls running when profiling is turned off aren't recorded
+    engine.eval(": off noprofile ;".to_string()).unwrap();
+    engine.eval("profile off".to_string()).unwrap();
+    assert!(engine.profile_frames.is_empty());
+
+    engine
+        .eval("profile 2 quad drop noprofile".to_string())
+        .unwrap();
+    assert_eq!(engine.profile["off"].inclusive_words, 0);
+    assert_eq!(engine.profile["quad"].inclusive_words, 7);
+}
+
+#[test]
+fn stack_effects() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    engine.eval(": sq ( n -- n2 ) dup * ;".to_string()).unwrap();
+    assert_eq!(engine.word_effect("sq").unwrap().to_string(), "( n -- n2 )");
+    let outcome = engine.eval("3 sq .".to_string()).unwrap();
+    assert_eq!(outcome.output, "9");
+
+    // Declared words are checked against each other
+    engine
+        .eval(": sum_sq ( a b -- c ) sq swap sq + ;".to_string())
+        .unwrap();
+    let err = engine
+        .eval(": bad ( a -- b c ) sq ;".to_string())
+        .unwrap_err();
+    assert!(err.starts_with(STACK_EFFECT_ERROR));
+    assert!(err.contains("ends with stack depth 1"));
+    assert_eq!(engine.status(), EvalStatus::Completed);
+    assert!(engine.word_effect("bad").is_none());
+
+    let err = engine.eval(": u ( -- a ) + ;".to_string()).unwrap_err();
+    assert!(err.contains("stack underflow at +"));
+
+    // Branches
+    engine
+        .eval(": sign ( n -- s ) 0 > if 1 else -1 then ;".to_string())
+        .unwrap();
+    let err = engine
+        .eval(": br ( a -- b ) if 1 else 2 3 then ;".to_string())
+        .unwrap_err();
+    assert!(err.contains("branches of if end with stack depth 1 and 2"));
+
+    // Loops
+    engine
+        .eval(": count ( n -- ) while dup 0 > do 1 - again drop ;".to_string())
+        .unwrap();
+    let err = engine
+        .eval(": lp ( -- ) 10 0 for i next ;".to_string())
+        .unwrap_err();
+    assert!(err.contains("for loop changes the stack depth"));
+    let err = engine
+        .eval(": ents ( map -- ) map-each map-next ;".to_string())
+        .unwrap_err();
+    assert!(err.contains("map-each loop changes the stack depth"));
+
+    // Words of declared structs
+    engine
+        .eval("struct point x y end-struct".to_string())
+        .unwrap();
+    engine
+        .eval(": right ( p -- p ) dup point.x 1 + point.x! ;".to_string())
+        .unwrap();
+    let err = engine
+        .eval(": mk ( a -- p ) point ;".to_string())
+        .unwrap_err();
+    assert!(err.contains("stack underflow at point"));
+
+    // Plain comments and words with an unknown effect aren't checked
+    engine
+        .eval(": a ( just a comment ) 1 ;".to_string())
+        .unwrap();
+    engine.eval(": b ( -- ) a ;".to_string()).unwrap();
+    assert!(engine.word_effect("a").is_none());
+
+    // Neither are overridden and excluded standard words
+    let err = engine
+        .eval(": first ( a b -- a ) swap ;".to_string())
+        .unwrap_err();
+    assert!(err.contains("ends with stack depth 2"));
+    engine.override_word("swap", |s| {
+        s.main_stack.pop();
+        Ok("".to_string())
+    });
+    engine
+        .eval(": first ( a b -- a ) swap ;".to_string())
+        .unwrap();
+    engine.exclude_words(&["rot"]);
+    engine.eval(": r ( a b c -- ) rot ;".to_string()).unwrap();
+}
+
+#[test]
+fn source_locations() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    engine.set_source("lib.sorth");
+    engine.eval(": inner 1 foo ;".to_string()).unwrap();
+    engine.eval("".to_string()).unwrap();
+    engine.eval(": outer\n  inner ;".to_string()).unwrap();
+
+    let inner = &engine.compiled_words[0];
+    assert_eq!(inner.span.0.to_string(), "lib.sorth:1:1");
+    assert_eq!(inner.span.1.to_string(), "lib.sorth:1:15");
+    assert_eq!(inner.locations[1].column, 11);
+
+    let outer = &engine.compiled_words[1];
+    assert_eq!(outer.span.0.line, 3);
+    assert_eq!(outer.locations[0].to_string(), "lib.sorth:4:3");
+
+    engine.set_source("main.sorth");
+    let err = engine.eval("2 outer".to_string()).unwrap_err();
+    assert_eq!(
+        err,
+        UNKNOWN_WORD_ERROR.to_string()
+            + "foo\n"
+            + "  at lib.sorth:1:11 in inner\n"
+            + "  at lib.sorth:4:3 in outer\n"
+            + "  at main.sorth:1:3\n"
+    );
+
+    engine.load("1 2 +".to_string()).unwrap();
+    let step = engine.step().unwrap().unwrap();
+    assert_eq!(step.location.to_string(), "main.sorth:2:1");
+}
+
+#[test]
+fn test_words() {
+    let mut engine = Engine::new();
+
+    let std_words = Standard::new();
+
+    engine.import_word_list(std_words);
+
+    let outcome = engine
+        .eval("7 T{ 2 3 + -> 5 }T T{ 1 2 swap -> 2 1 }T 4 4 assert-eq -1 assert".to_string())
+        .unwrap();
+    assert_eq!(outcome.stack, vec![Types::Int(7)]);
+    assert_eq!(engine.tests_passed, 4);
+
+    let err = engine.eval("T{ 2 2 + -> 5 }T".to_string()).unwrap_err();
+    assert_eq!(
+        err.lines().next().unwrap(),
+        TEST_FAILED_ERROR.to_string() + "expected [ 5 ] got [ 4 ]"
+    );
+    assert_eq!(engine.main_stack, vec![Types::Int(7)]);
+
+    let err = engine
+        .eval("\" a \" \" b \" assert-eq".to_string())
+        .unwrap_err();
+    assert_eq!(
+        err.lines().next().unwrap(),
+        TEST_FAILED_ERROR.to_string() + "expected b got a"
+    );
+
+    assert!(engine.eval("0 assert".to_string()).is_err());
+    assert_eq!(engine.tests_failed, 3);
+
+    engine.main_stack.clear();
+    assert!(engine.eval("5 assert-eq".to_string()).is_err());
+    assert_eq!(engine.main_stack, vec![Types::Int(5)]);
+
+    assert!(engine.eval("1 -> }T".to_string()).is_err());
+}
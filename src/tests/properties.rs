@@ -0,0 +1,386 @@
+// Property tests for the numeric words
+//
+// The expected result types are derived from `rank`, which restates the
+// numeric tower documented in types.rs independently of `Types::promote`.
+
+use std::collections::BTreeMap;
+
+use proptest::prelude::*;
+
+use super::{
+    Decimal, Engine, Standard, Types, WordList, DIVISION_BY_ZERO_ERROR, NOT_A_FLAG_ERROR,
+    OVERFLOW_ERROR,
+};
+
+fn decimal() -> impl Strategy<Value = Decimal> {
+    (any::<i64>(), 0..=Decimal::MAX_SCALE)
+        .prop_map(|(mantissa, scale)| Decimal::new(mantissa as i128, scale).unwrap())
+}
+
+fn number() -> impl Strategy<Value = Types> {
+    prop_oneof![
+        any::<u8>().prop_map(Types::Byte),
+        any::<i32>().prop_map(Types::Int),
+        any::<u32>().prop_map(Types::UInt),
+        any::<i64>().prop_map(Types::Long),
+        any::<u64>().prop_map(Types::ULong),
+        any::<i128>().prop_map(Types::Int128),
+        decimal().prop_map(Types::Decimal),
+        any::<f32>().prop_map(Types::Float),
+        any::<f64>().prop_map(Types::Double),
+    ]
+}
+
+// Runs `word` on the given stack and returns the resulting stack
+fn apply(stack: &[Types], word: &str) -> Result<Vec<Types>, String> {
+    let mut engine = Engine::new();
+    engine.import_word_list(Standard::new());
+    engine.main_stack = stack.to_vec().into();
+    engine.eval(word.to_string()).map(|outcome| outcome.stack)
+}
+
+// Signedness and width of an integer, None for floats
+fn integer(val: &Types) -> Option<(bool, u32)> {
+    match val {
+        Types::Byte(_) => Some((false, 8)),
+        Types::Int(_) => Some((true, 32)),
+        Types::UInt(_) => Some((false, 32)),
+        Types::Long(_) => Some((true, 64)),
+        Types::ULong(_) => Some((false, 64)),
+        Types::Int128(_) => Some((true, 128)),
+        _ => None,
+    }
+}
+
+fn kind(val: &Types) -> &'static str {
+    match val {
+        Types::Byte(_) => "byte",
+        Types::Int(_) => "int",
+        Types::UInt(_) => "uint",
+        Types::Long(_) => "long",
+        Types::ULong(_) => "ulong",
+        Types::Int128(_) => "int128",
+        #[cfg(feature = "bigint")]
+        Types::BigInt(_) => "bigint",
+        Types::Decimal(_) => "decimal",
+        Types::Float(_) => "float",
+        Types::Double(_) => "double",
+        Types::Bool(_)
+        | Types::Char(_)
+        | Types::Str(_)
+        | Types::List(_)
+        | Types::Map(_)
+        | Types::Struct(_)
+        | Types::VarRef(_) => {
+            unreachable!()
+        }
+    }
+}
+
+fn result_kind(a: &Types, b: &Types) -> &'static str {
+    match (integer(a), integer(b)) {
+        (Some((a_signed, a_bits)), Some((b_signed, b_bits))) => {
+            if a_signed == b_signed {
+                return kind(if a_bits >= b_bits { a } else { b });
+            }
+            // A signed type needs one more bit to hold an unsigned one
+            let (signed, unsigned) = if a_signed {
+                (a_bits, b_bits)
+            } else {
+                (b_bits, a_bits)
+            };
+            match signed.max(unsigned + 1) {
+                0..=32 => "int",
+                33..=64 => "long",
+                _ => "int128",
+            }
+        }
+        // Only a Byte fits into a Float, and integers up to 64 bits into a
+        // Decimal
+        _ => match (kind(a), kind(b)) {
+            ("float", "float" | "byte") | ("byte", "float") => "float",
+            ("decimal", _) if decimal_holds(b) => "decimal",
+            (_, "decimal") if decimal_holds(a) => "decimal",
+            _ => "double",
+        },
+    }
+}
+
+fn decimal_holds(val: &Types) -> bool {
+    kind(val) == "decimal" || integer(val).is_some_and(|(_, bits)| bits <= 64)
+}
+
+// Equality that treats all NaNs as equal
+fn same(a: &Types, b: &Types) -> bool {
+    match (a, b) {
+        (Types::Float(a), Types::Float(b)) => a == b || (a.is_nan() && b.is_nan()),
+        (Types::Double(a), Types::Double(b)) => a == b || (a.is_nan() && b.is_nan()),
+        _ => a == b,
+    }
+}
+
+fn flag(stack: Vec<Types>) -> bool {
+    match stack.as_slice() {
+        [Types::Bool(flag)] => *flag,
+        _ => panic!("not a flag: {:?}", stack),
+    }
+}
+
+proptest! {
+    #[test]
+    fn add_and_multiply_commute(a in number(), b in number()) {
+        for word in ["+", "*", "wrapping +", "wrapping *", "saturating +", "saturating *"] {
+            match (apply(&[a.clone(), b.clone()], word), apply(&[b.clone(), a.clone()], word)) {
+                (Ok(ab), Ok(ba)) => prop_assert!(same(&ab[0], &ba[0]), "{:?} {:?} {}", a, b, word),
+                (Err(ab), Err(ba)) => prop_assert_eq!(ab, ba),
+                (ab, ba) => prop_assert!(false, "{:?} {:?}", ab, ba),
+            }
+        }
+    }
+
+    #[test]
+    fn results_follow_the_numeric_tower(a in number(), b in number()) {
+        for word in ["+", "-", "*", "/", "%"] {
+            match apply(&[a.clone(), b.clone()], word) {
+                Ok(stack) => {
+                    prop_assert_eq!(stack.len(), 1);
+                    // With the bigint feature results that overflow are BigInts
+                    let kind = kind(&stack[0]);
+                    prop_assert!(kind == result_kind(&a, &b) || kind == "bigint", "{:?} {:?} {}", a, b, word);
+                }
+                Err(err) => prop_assert!(
+                    err.starts_with(DIVISION_BY_ZERO_ERROR) || err.starts_with(OVERFLOW_ERROR),
+                    "{}",
+                    err
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn arithmetic_modes_agree_without_overflow(a in any::<i32>(), b in any::<i32>()) {
+        let exact = |op: fn(i64, i64) -> i64| op(a as i64, b as i64);
+        for (word, result) in [
+            ("+", exact(|a, b| a + b)),
+            ("-", exact(|a, b| a - b)),
+            ("*", exact(|a, b| a * b)),
+        ] {
+            let checked = apply(&[Types::Int(a), Types::Int(b)], word);
+            let wrapping = apply(&[Types::Int(a), Types::Int(b)], &format!("wrapping {}", word)).unwrap();
+            let saturating = apply(&[Types::Int(a), Types::Int(b)], &format!("saturating {}", word)).unwrap();
+
+            prop_assert_eq!(&wrapping, &vec![Types::Int(result as i32)]);
+            prop_assert_eq!(
+                &saturating,
+                &vec![Types::Int(result.clamp(i32::MIN as i64, i32::MAX as i64) as i32)]
+            );
+            match i32::try_from(result) {
+                Ok(result) => prop_assert_eq!(checked.unwrap(), vec![Types::Int(result)]),
+                #[cfg(not(feature = "bigint"))]
+                Err(_) => prop_assert!(checked.unwrap_err().starts_with(OVERFLOW_ERROR)),
+                #[cfg(feature = "bigint")]
+                Err(_) => prop_assert_eq!(checked.unwrap(), vec![Types::BigInt(result.into())]),
+            }
+        }
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn bigints_agree_with_int128s(a in any::<i64>(), b in any::<i64>()) {
+        let big = [Types::BigInt(a.into()), Types::BigInt(b.into())];
+        let exact = |op: fn(i128, i128) -> i128| Types::BigInt(op(a as i128, b as i128).into());
+        for (word, result) in [
+            ("+", exact(|a, b| a + b)),
+            ("-", exact(|a, b| a - b)),
+            ("*", exact(|a, b| a * b)),
+            ("min", exact(|a, b| a.min(b))),
+            ("to_str to_int128 +", exact(|a, b| a + b)),
+        ] {
+            prop_assert_eq!(apply(&big, word).unwrap(), vec![result]);
+        }
+        if b != 0 {
+            prop_assert_eq!(apply(&big, "/").unwrap(), vec![exact(|a, b| a / b)]);
+            prop_assert_eq!(apply(&big, "%").unwrap(), vec![exact(|a, b| a % b)]);
+        }
+        prop_assert_eq!(
+            flag(apply(&big, "<").unwrap()),
+            a > b
+        );
+    }
+
+    #[test]
+    fn comparisons_agree(a in number(), b in number()) {
+        let eq = flag(apply(&[a.clone(), b.clone()], "==").unwrap());
+        let ne = flag(apply(&[a.clone(), b.clone()], "!=").unwrap());
+        let gt = flag(apply(&[a.clone(), b.clone()], ">").unwrap());
+        let lt = flag(apply(&[a.clone(), b.clone()], "<").unwrap());
+        let swapped_lt = flag(apply(&[b.clone(), a.clone()], "<").unwrap());
+
+        prop_assert_eq!(eq, !ne);
+        prop_assert_eq!(gt, swapped_lt);
+        // At most one holds, exactly one unless a NaN is involved
+        prop_assert!(u8::from(eq) + u8::from(gt) + u8::from(lt) <= 1);
+    }
+
+    #[test]
+    fn integers_are_flags(a in number()) {
+        let stack = apply(std::slice::from_ref(&a), "not");
+        match a {
+            Types::Decimal(_) | Types::Float(_) | Types::Double(_) => {
+                prop_assert!(stack.unwrap_err().starts_with(NOT_A_FLAG_ERROR))
+            }
+            _ => prop_assert_eq!(flag(stack.unwrap()), a.truthy() == Some(false)),
+        }
+        // Only a NaN isn't equal to itself
+        prop_assert_eq!(flag(apply(&[a.clone(), a.clone()], "==").unwrap()), a == a);
+    }
+
+    #[test]
+    fn ints_mixed_with_floats_stay_exact(a in any::<i32>()) {
+        let stack = apply(&[Types::Int(a), Types::Float(0.0)], "+ to_int").unwrap();
+        prop_assert_eq!(stack, vec![Types::Int(a)]);
+    }
+
+    #[test]
+    fn ints_round_trip_through_strings(a in any::<i32>()) {
+        let stack = apply(&[Types::Int(a)], "to_str to_int").unwrap();
+        prop_assert_eq!(stack, vec![Types::Int(a)]);
+    }
+
+    #[test]
+    fn longs_round_trip_through_strings(a in any::<i64>()) {
+        let stack = apply(&[Types::Long(a)], "to_str to_long").unwrap();
+        prop_assert_eq!(stack, vec![Types::Long(a)]);
+    }
+
+    #[test]
+    fn bytes_round_trip_through_strings(a in any::<u8>()) {
+        let stack = apply(&[Types::Byte(a)], "to_str to_byte").unwrap();
+        prop_assert_eq!(stack, vec![Types::Byte(a)]);
+    }
+
+    #[test]
+    fn unsigned_round_trip_through_strings(a in any::<u32>(), b in any::<u64>()) {
+        let stack = apply(&[Types::UInt(a), Types::ULong(b)], "to_str to_ulong swap to_str to_uint").unwrap();
+        prop_assert_eq!(stack, vec![Types::ULong(b), Types::UInt(a)]);
+    }
+
+    #[test]
+    fn int128s_round_trip_through_strings(a in any::<i128>()) {
+        let stack = apply(&[Types::Int128(a)], "to_str to_int128").unwrap();
+        prop_assert_eq!(stack, vec![Types::Int128(a)]);
+    }
+
+    #[test]
+    fn floats_round_trip_through_strings(a in any::<f32>()) {
+        let stack = apply(&[Types::Float(a)], "to_str to_float").unwrap();
+        prop_assert!(same(&stack[0], &Types::Float(a)));
+    }
+
+    #[test]
+    fn doubles_round_trip_through_strings(a in any::<f64>()) {
+        let stack = apply(&[Types::Double(a)], "to_str to_double").unwrap();
+        prop_assert!(same(&stack[0], &Types::Double(a)));
+    }
+
+    #[test]
+    fn decimals_round_trip_through_strings(a in decimal()) {
+        let stack = apply(&[Types::Decimal(a)], "to_str to_decimal").unwrap();
+        prop_assert_eq!(&stack, &vec![Types::Decimal(a)]);
+        // Trailing zeros are kept as well
+        prop_assert_eq!(stack[0].to_string(), a.to_string());
+    }
+
+    #[test]
+    fn decimal_addition_is_exact(a in any::<i64>(), b in any::<i64>(), scale in 0..=Decimal::MAX_SCALE) {
+        let stack = [
+            Types::Decimal(Decimal::new(a as i128, scale).unwrap()),
+            Types::Decimal(Decimal::new(b as i128, scale).unwrap()),
+        ];
+        let exact = |result: i128| vec![Types::Decimal(Decimal::new(result, scale).unwrap())];
+        prop_assert_eq!(apply(&stack, "+").unwrap(), exact(a as i128 + b as i128));
+        prop_assert_eq!(apply(&stack, "-").unwrap(), exact(a as i128 - b as i128));
+    }
+
+    #[test]
+    fn decimal_division_undoes_multiplication(
+        a in any::<i32>(),
+        a_scale in 0..=9u32,
+        b in any::<i32>().prop_filter("non zero", |b| *b != 0),
+        b_scale in 0..=9u32,
+    ) {
+        let a = Types::Decimal(Decimal::new(a as i128, a_scale).unwrap());
+        let b = Types::Decimal(Decimal::new(b as i128, b_scale).unwrap());
+        let stack = apply(&[b.clone(), a.clone(), b], "* swap /").unwrap();
+        prop_assert_eq!(stack, vec![a]);
+    }
+
+    #[test]
+    fn doubles_convert_to_decimals_exactly(a in -1e12f64..1e12) {
+        // Smaller values can have more digits than a Decimal keeps
+        prop_assume!(a == 0.0 || a.abs() >= 1.0);
+        let stack = apply(&[Types::Double(a)], "to_decimal to_double").unwrap();
+        prop_assert_eq!(stack, vec![Types::Double(a)]);
+    }
+
+    #[test]
+    fn chars_round_trip_through_code_points(c in any::<char>()) {
+        let literal = format!("'\\u{{{:x}}}'", c as u32);
+        let stack = apply(&[], &format!("{} char>int int>char {}", literal, literal)).unwrap();
+        prop_assert_eq!(stack, vec![Types::Char(c), Types::Char(c)]);
+    }
+
+    #[test]
+    fn strings_explode_into_their_chars(a in "\\PC*") {
+        let stack = apply(&[Types::Str(a.clone())], "chars").unwrap();
+        prop_assert_eq!(stack, a.chars().map(Types::Char).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn sorting_orders_lists(a in proptest::collection::vec(any::<i64>(), 0..20)) {
+        let list = Types::List(a.iter().copied().map(Types::Long).collect());
+        let mut sorted = a.clone();
+        sorted.sort();
+
+        let stack = apply(std::slice::from_ref(&list), "sort").unwrap();
+        prop_assert_eq!(stack, vec![Types::List(sorted.into_iter().map(Types::Long).collect())]);
+        let stack = apply(std::slice::from_ref(&list), "reverse reverse").unwrap();
+        prop_assert_eq!(stack, vec![list]);
+    }
+
+    #[test]
+    fn slices_concat_to_the_whole_list(a in proptest::collection::vec(any::<i32>(), 0..20), at in 0..20usize) {
+        let at = at.min(a.len());
+        let list = Types::List(a.iter().copied().map(Types::Int).collect());
+        let program = format!("dup 0 {} slice swap {} {} slice concat", at, at, a.len());
+        prop_assert_eq!(apply(std::slice::from_ref(&list), &program).unwrap(), vec![list]);
+    }
+
+    #[test]
+    fn maps_keep_the_last_value_put(a in proptest::collection::vec((any::<i8>(), any::<i32>()), 0..20)) {
+        let program: String = a
+            .iter()
+            .map(|(key, val)| format!(" {} {} map-put", key, val))
+            .collect();
+        let expected: BTreeMap<i8, i32> = a.iter().copied().collect();
+
+        let stack = apply(&[], &format!("map-new{} dup map-keys swap map-values", program)).unwrap();
+        prop_assert_eq!(stack, vec![
+            Types::List(expected.keys().map(|&key| Types::Int(key as i32)).collect()),
+            Types::List(expected.values().copied().map(Types::Int).collect()),
+        ]);
+    }
+
+    #[test]
+    fn integers_convert_to_doubles_exactly(a in any::<i32>()) {
+        let stack = apply(&[Types::Int(a)], "to_double to_int").unwrap();
+        prop_assert_eq!(stack, vec![Types::Int(a)]);
+    }
+
+    #[test]
+    fn unary_words_never_panic(a in number()) {
+        for word in ["abs", "not", "to_int", "to_long", "to_float", "to_double", "to_byte", "to_uint", "to_ulong", "to_int128", "to_decimal", "to_str", "rescale", "binvert", "popcount"] {
+            let _ = apply(std::slice::from_ref(&a), word);
+        }
+    }
+}
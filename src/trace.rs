@@ -0,0 +1,78 @@
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::prelude::{Engine, Types};
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum TracePhase {
+    Before,
+    After,
+}
+
+/// Passed to the trace hook before and after every executed word.
+pub struct TraceEvent<'a> {
+    pub phase: TracePhase,
+    /// The executed word as written in the source.
+    pub token: &'a str,
+    /// Name of the matched word list entry, e.g. `dup`, `<int>` or `<custom word>`.
+    pub name: &'a str,
+    /// Number of custom words being executed.
+    pub depth: usize,
+    /// Value of `Engine::executed_words`, increases with every executed word.
+    pub step: u64,
+    pub stack: &'a [Types],
+}
+
+pub type TraceHook = Box<dyn FnMut(&TraceEvent)>;
+
+impl fmt::Display for TraceEvent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let phase = match self.phase {
+            TracePhase::Before => '>',
+            TracePhase::After => '<',
+        };
+
+        write!(
+            f,
+            "{:>8} {}{} {} [",
+            self.step,
+            "  ".repeat(self.depth),
+            phase,
+            self.token
+        )?;
+        for val in self.stack {
+            write!(f, " {}", val)?;
+        }
+        write!(f, " ]")
+    }
+}
+
+impl Engine {
+    /// Sets the function called before and after every executed word while
+    /// tracing is enabled, either with `engine.tracing = true` or the `trace` word.
+    pub fn set_trace_hook(&mut self, hook: impl FnMut(&TraceEvent) + 'static) {
+        self.trace_hook = Some(Box::new(hook));
+    }
+
+    pub(crate) fn trace(&mut self, phase: TracePhase, name: &str, token: &str, depth: usize) {
+        let mut hook = match self.trace_hook.take() {
+            Some(hook) => hook,
+            None => return,
+        };
+
+        hook(&TraceEvent {
+            phase,
+            token,
+            name,
+            depth,
+            step: self.executed_words,
+            stack: &self.main_stack,
+        });
+
+        self.trace_hook = Some(hook);
+    }
+
+    pub(crate) fn call_depth(&self) -> usize {
+        self.compiled_exec.iter().filter(|&&c| c).count()
+    }
+}
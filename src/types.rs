@@ -1,16 +1,397 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use crate::decimal::Decimal;
+#[cfg(feature = "bigint")]
+use num_traits::{ToPrimitive, Zero};
+
 pub type Int = i32;
 pub type Long = i64;
 pub type Float = f32;
 pub type Double = f64;
 pub type Byte = u8;
+pub type UInt = u32;
+pub type ULong = u64;
+pub type Int128 = i128;
+#[cfg(feature = "bigint")]
+pub type BigInt = num_bigint::BigInt;
 pub type Str = String;
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Types {
     Int(Int),
     Long(Long),
     Float(Float),
     Double(Double),
     Byte(Byte),
+    UInt(UInt),
+    ULong(ULong),
+    Int128(Int128),
+    #[cfg(feature = "bigint")]
+    BigInt(BigInt),
+    Decimal(Decimal),
+    Bool(bool),
+    Char(char),
+    Str(Str),
+    List(Vec<Types>),
+    Map(BTreeMap<MapKey, Types>),
+    Struct(Struct),
+    VarRef(VarRef),
+}
+
+/// Handle to a variable pushed by `@<name>`. The variable keeps its place in
+/// `Engine::variable_stack` when it is declared again, so a handle stays
+/// valid for as long as the engine knows the variable.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VarRef {
+    pub index: usize,
+    pub name: String,
+}
+
+/// Value of a struct declared with `struct`, the fields are in the order they
+/// were declared in.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Struct {
+    pub name: String,
+    pub fields: Vec<(String, Types)>,
+}
+
+/// Key of a `Types::Map`, a string or an integer. Integer keys are compared
+/// by their value whatever their type, so `1` and `1l` are the same key.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MapKey {
+    Int(Int128),
     Str(Str),
 }
+
+impl TryFrom<Types> for MapKey {
+    type Error = Types;
+
+    fn try_from(val: Types) -> Result<MapKey, Types> {
+        match val {
+            Types::Byte(a) => Ok(MapKey::Int(a as Int128)),
+            Types::Int(a) => Ok(MapKey::Int(a as Int128)),
+            Types::UInt(a) => Ok(MapKey::Int(a as Int128)),
+            Types::Long(a) => Ok(MapKey::Int(a as Int128)),
+            Types::ULong(a) => Ok(MapKey::Int(a as Int128)),
+            Types::Int128(a) => Ok(MapKey::Int(a)),
+            #[cfg(feature = "bigint")]
+            Types::BigInt(a) => match a.to_i128() {
+                Some(a) => Ok(MapKey::Int(a)),
+                None => Err(Types::BigInt(a)),
+            },
+            Types::Str(a) => Ok(MapKey::Str(a)),
+            val => Err(val),
+        }
+    }
+}
+
+// Integer keys come back as the first of Int, Long and Int128 that holds them
+impl From<MapKey> for Types {
+    fn from(key: MapKey) -> Types {
+        match key {
+            MapKey::Int(a) => match (Int::try_from(a), Long::try_from(a)) {
+                (Ok(a), _) => Types::Int(a),
+                (_, Ok(a)) => Types::Long(a),
+                _ => Types::Int128(a),
+            },
+            MapKey::Str(a) => Types::Str(a),
+        }
+    }
+}
+
+impl From<Int> for MapKey {
+    fn from(key: Int) -> MapKey {
+        MapKey::Int(key as Int128)
+    }
+}
+
+impl From<Long> for MapKey {
+    fn from(key: Long) -> MapKey {
+        MapKey::Int(key as Int128)
+    }
+}
+
+impl From<&str> for MapKey {
+    fn from(key: &str) -> MapKey {
+        MapKey::Str(key.into())
+    }
+}
+
+impl From<Str> for MapKey {
+    fn from(key: Str) -> MapKey {
+        MapKey::Str(key)
+    }
+}
+
+// Strings and chars inside lists, maps and structs are written like their literals
+fn write_literal(f: &mut core::fmt::Formatter<'_>, val: &Types) -> core::fmt::Result {
+    match val {
+        Types::Str(val) => write!(f, " \" {} \"", val),
+        Types::Char(val) => write!(f, " '{}'", val),
+        val => write!(f, " {}", val),
+    }
+}
+
+impl core::fmt::Display for Types {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Types::Int(a) => write!(f, "{}", a),
+            Types::Long(a) => write!(f, "{}", a),
+            Types::Float(a) => write!(f, "{}", a),
+            Types::Double(a) => write!(f, "{}", a),
+            Types::Byte(a) => write!(f, "{}", a),
+            Types::UInt(a) => write!(f, "{}", a),
+            Types::ULong(a) => write!(f, "{}", a),
+            Types::Int128(a) => write!(f, "{}", a),
+            #[cfg(feature = "bigint")]
+            Types::BigInt(a) => write!(f, "{}", a),
+            Types::Decimal(a) => write!(f, "{}", a),
+            Types::Bool(a) => write!(f, "{}", a),
+            Types::Char(a) => write!(f, "{}", a),
+            Types::Str(a) => write!(f, "{}", a),
+            Types::List(a) => {
+                write!(f, "{{")?;
+                for val in a.iter() {
+                    write_literal(f, val)?;
+                }
+                write!(f, " }}")
+            }
+            Types::Map(a) => {
+                write!(f, "map{{")?;
+                for (key, val) in a.iter() {
+                    write_literal(f, &key.clone().into())?;
+                    write!(f, " =>")?;
+                    write_literal(f, val)?;
+                }
+                write!(f, " }}")
+            }
+            Types::VarRef(a) => write!(f, "@{}", a.name),
+            Types::Struct(a) => {
+                write!(f, "{}{{", a.name)?;
+                for (field, val) in a.fields.iter() {
+                    write!(f, " {}:", field)?;
+                    write_literal(f, val)?;
+                }
+                write!(f, " }}")
+            }
+        }
+    }
+}
+
+// Numeric tower
+//
+//     Byte     holds Byte
+//     Int      holds Byte, Int
+//     UInt     holds Byte, UInt
+//     Long     holds Byte, Int, UInt, Long
+//     ULong    holds Byte, UInt, ULong
+//     Int128   holds every fixed width integer
+//     BigInt   holds every integer (bigint feature)
+//     Decimal  holds Byte, Int, UInt, Long, ULong, Decimal
+//     Float    holds Byte, Float
+//     Double   holds every number
+//
+// Binary operations on two different number types first convert both operands
+// to the lowest type on the tower that holds both of them. An integer type
+// holds another one if it can represent all of its values, so an Int mixed with
+// a UInt gives a Long and a Long mixed with a ULong an Int128. Any integer
+// wider than a Byte mixed with a Float gives a Double because a Float can't
+// hold all of its values, for a BigInt the Double is only an approximation.
+// A Decimal and a Float or an integer wider than 64 bit don't hold each other
+// either, so they mix to a Double too.
+
+/// Two numbers converted to a common type of the numeric tower.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Promoted {
+    Byte(Byte, Byte),
+    Int(Int, Int),
+    UInt(UInt, UInt),
+    Long(Long, Long),
+    ULong(ULong, ULong),
+    Int128(Int128, Int128),
+    #[cfg(feature = "bigint")]
+    BigInt(BigInt, BigInt),
+    Decimal(Decimal, Decimal),
+    Float(Float, Float),
+    Double(Double, Double),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Byte,
+    Int,
+    UInt,
+    Long,
+    ULong,
+    Int128,
+    #[cfg(feature = "bigint")]
+    BigInt,
+    Decimal,
+    Float,
+    Double,
+}
+
+// From the bottom of the tower
+#[cfg(not(feature = "bigint"))]
+const TOWER: [Kind; 9] = [
+    Kind::Byte,
+    Kind::Int,
+    Kind::UInt,
+    Kind::Long,
+    Kind::ULong,
+    Kind::Int128,
+    Kind::Decimal,
+    Kind::Float,
+    Kind::Double,
+];
+
+#[cfg(feature = "bigint")]
+const TOWER: [Kind; 10] = [
+    Kind::Byte,
+    Kind::Int,
+    Kind::UInt,
+    Kind::Long,
+    Kind::ULong,
+    Kind::Int128,
+    Kind::BigInt,
+    Kind::Decimal,
+    Kind::Float,
+    Kind::Double,
+];
+
+impl Kind {
+    fn holds(self, other: Kind) -> bool {
+        match self {
+            Kind::Byte => other == Kind::Byte,
+            Kind::Int => matches!(other, Kind::Byte | Kind::Int),
+            Kind::UInt => matches!(other, Kind::Byte | Kind::UInt),
+            Kind::Long => matches!(other, Kind::Byte | Kind::Int | Kind::UInt | Kind::Long),
+            Kind::ULong => matches!(other, Kind::Byte | Kind::UInt | Kind::ULong),
+            Kind::Int128 => matches!(
+                other,
+                Kind::Byte | Kind::Int | Kind::UInt | Kind::Long | Kind::ULong | Kind::Int128
+            ),
+            #[cfg(feature = "bigint")]
+            Kind::BigInt => !matches!(other, Kind::Decimal | Kind::Float | Kind::Double),
+            Kind::Decimal => matches!(
+                other,
+                Kind::Byte | Kind::Int | Kind::UInt | Kind::Long | Kind::ULong | Kind::Decimal
+            ),
+            Kind::Float => matches!(other, Kind::Byte | Kind::Float),
+            Kind::Double => true,
+        }
+    }
+}
+
+impl Types {
+    fn kind(&self) -> Option<Kind> {
+        match self {
+            Types::Byte(_) => Some(Kind::Byte),
+            Types::Int(_) => Some(Kind::Int),
+            Types::UInt(_) => Some(Kind::UInt),
+            Types::Long(_) => Some(Kind::Long),
+            Types::ULong(_) => Some(Kind::ULong),
+            Types::Int128(_) => Some(Kind::Int128),
+            #[cfg(feature = "bigint")]
+            Types::BigInt(_) => Some(Kind::BigInt),
+            Types::Decimal(_) => Some(Kind::Decimal),
+            Types::Float(_) => Some(Kind::Float),
+            Types::Double(_) => Some(Kind::Double),
+            Types::Bool(_)
+            | Types::Char(_)
+            | Types::Str(_)
+            | Types::List(_)
+            | Types::Map(_)
+            | Types::Struct(_)
+            | Types::VarRef(_) => None,
+        }
+    }
+
+    /// Truth value of a flag. A Bool is a flag and so is every integer, zero
+    /// is false and any other value true. `None` for every other type.
+    pub fn truthy(&self) -> Option<bool> {
+        match self {
+            Types::Bool(a) => Some(*a),
+            Types::Byte(a) => Some(*a != 0),
+            Types::Int(a) => Some(*a != 0),
+            Types::UInt(a) => Some(*a != 0),
+            Types::Long(a) => Some(*a != 0),
+            Types::ULong(a) => Some(*a != 0),
+            Types::Int128(a) => Some(*a != 0),
+            #[cfg(feature = "bigint")]
+            Types::BigInt(a) => Some(!a.is_zero()),
+            _ => None,
+        }
+    }
+
+    // Every integer fits into an Int128, so the narrowing casts from it in
+    // `promote` are exact
+    fn cast_int128(&self) -> Int128 {
+        match *self {
+            Types::Byte(a) => a as Int128,
+            Types::Int(a) => a as Int128,
+            Types::UInt(a) => a as Int128,
+            Types::Long(a) => a as Int128,
+            Types::ULong(a) => a as Int128,
+            Types::Int128(a) => a,
+            _ => unreachable!(),
+        }
+    }
+
+    #[cfg(feature = "bigint")]
+    fn cast_bigint(&self) -> BigInt {
+        match self {
+            Types::BigInt(a) => a.clone(),
+            _ => BigInt::from(self.cast_int128()),
+        }
+    }
+
+    fn cast_decimal(&self) -> Decimal {
+        match self {
+            Types::Decimal(a) => *a,
+            _ => Decimal::from(self.cast_int128()),
+        }
+    }
+
+    fn cast_double(&self) -> Double {
+        match self {
+            Types::Float(a) => *a as Double,
+            Types::Double(a) => *a,
+            // Never None, too large values become infinite
+            #[cfg(feature = "bigint")]
+            Types::BigInt(a) => a.to_f64().unwrap_or(Double::NAN),
+            Types::Decimal(a) => a.to_double(),
+            _ => self.cast_int128() as Double,
+        }
+    }
+
+    fn cast_float(&self) -> Float {
+        match self {
+            Types::Float(a) => *a,
+            _ => self.cast_int128() as Float,
+        }
+    }
+
+    /// Converts two numbers to their common type on the numeric tower, `None`
+    /// if one of them isn't a number.
+    pub fn promote(a: &Types, b: &Types) -> Option<Promoted> {
+        let (kind_a, kind_b) = (a.kind()?, b.kind()?);
+        let kind = TOWER
+            .into_iter()
+            .find(|kind| kind.holds(kind_a) && kind.holds(kind_b))?;
+
+        Some(match kind {
+            Kind::Byte => Promoted::Byte(a.cast_int128() as Byte, b.cast_int128() as Byte),
+            Kind::Int => Promoted::Int(a.cast_int128() as Int, b.cast_int128() as Int),
+            Kind::UInt => Promoted::UInt(a.cast_int128() as UInt, b.cast_int128() as UInt),
+            Kind::Long => Promoted::Long(a.cast_int128() as Long, b.cast_int128() as Long),
+            Kind::ULong => Promoted::ULong(a.cast_int128() as ULong, b.cast_int128() as ULong),
+            Kind::Int128 => Promoted::Int128(a.cast_int128(), b.cast_int128()),
+            #[cfg(feature = "bigint")]
+            Kind::BigInt => Promoted::BigInt(a.cast_bigint(), b.cast_bigint()),
+            Kind::Decimal => Promoted::Decimal(a.cast_decimal(), b.cast_decimal()),
+            Kind::Float => Promoted::Float(a.cast_float(), b.cast_float()),
+            Kind::Double => Promoted::Double(a.cast_double(), b.cast_double()),
+        })
+    }
+}
@@ -1,8 +1,10 @@
 use super::prelude::Engine;
+use alloc::{string::String, vec::Vec};
 
+pub type WordName = &'static str;
 pub type WordSymbol = fn(s: &Engine) -> bool;
 pub type WordDefinition = fn(s: &mut Engine) -> Result<String, String>;
-pub type Word = (WordSymbol, WordDefinition);
+pub type Word = (WordName, WordSymbol, WordDefinition);
 
 pub trait WordList {
     fn new() -> Self;